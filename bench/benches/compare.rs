@@ -0,0 +1,93 @@
+//! Compares case-insensitive equality against the standard library.
+//!
+//! Each input pair is compared with `UniCase`, `Ascii` and `unicase::eq`,
+//! and with two baselines: lowercasing both sides with `to_lowercase`, and
+//! `str::eq_ignore_ascii_case`. The pairs are equal ignoring case, so the
+//! Unicode-aware comparisons can't stop early; `Ascii` and
+//! `eq_ignore_ascii_case` give up at the first non-ASCII difference. All
+//! inputs go through `black_box` so the work can't be done at compile time.
+//!
+//! Run with `cargo bench --bench compare` from the `bench` directory.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use unicase::{Ascii, UniCase};
+
+fn inputs() -> Vec<(&'static str, String, String)> {
+    let short_ascii = "Content-Type";
+    let long_ascii = "The Quick Brown Fox Jumps Over The Lazy Dog. ".repeat(32);
+    let short_unicode = "Ünïcödé";
+    let long_unicode = "Ünïcödé Σίσυφος Ἀθῆναι Привет Мир. ".repeat(32);
+    vec![
+        (
+            "short_ascii",
+            short_ascii.to_owned(),
+            short_ascii.to_uppercase(),
+        ),
+        ("long_ascii", long_ascii.clone(), long_ascii.to_uppercase()),
+        (
+            "short_unicode",
+            short_unicode.to_owned(),
+            short_unicode.to_uppercase(),
+        ),
+        (
+            "long_unicode",
+            long_unicode.clone(),
+            long_unicode.to_uppercase(),
+        ),
+    ]
+}
+
+fn bench_eq(c: &mut Criterion) {
+    let mut group = c.benchmark_group("eq");
+    for (name, a, b) in inputs() {
+        group.throughput(Throughput::Bytes(a.len() as u64));
+        let pair = (a.as_str(), b.as_str());
+
+        group.bench_with_input(
+            BenchmarkId::new("UniCase::new", name),
+            &pair,
+            |bench, &(a, b)| {
+                bench.iter(|| UniCase::new(black_box(a)) == UniCase::new(black_box(b)))
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("unicase::eq", name),
+            &pair,
+            |bench, &(a, b)| bench.iter(|| unicase::eq(black_box(a), black_box(b))),
+        );
+        group.bench_with_input(BenchmarkId::new("Ascii", name), &pair, |bench, &(a, b)| {
+            bench.iter(|| Ascii::new(black_box(a)) == Ascii::new(black_box(b)))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("to_lowercase", name),
+            &pair,
+            |bench, &(a, b)| {
+                bench.iter(|| black_box(a).to_lowercase() == black_box(b).to_lowercase())
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("eq_ignore_ascii_case", name),
+            &pair,
+            |bench, &(a, b)| bench.iter(|| black_box(a).eq_ignore_ascii_case(black_box(b))),
+        );
+    }
+    group.finish();
+}
+
+fn bench_prebuilt(c: &mut Criterion) {
+    // the mode check done once up front, as for long-lived keys
+    let mut group = c.benchmark_group("eq_prebuilt");
+    for (name, a, b) in inputs() {
+        group.throughput(Throughput::Bytes(a.len() as u64));
+        let pair = (UniCase::new(a.as_str()), UniCase::new(b.as_str()));
+        group.bench_with_input(BenchmarkId::new("UniCase", name), &pair, |bench, (a, b)| {
+            bench.iter(|| black_box(a) == black_box(b))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_eq, bench_prebuilt);
+criterion_main!(benches);
@@ -0,0 +1,39 @@
+extern crate autocfg;
+
+use std::env;
+
+fn main() {
+    let ac = autocfg::new();
+
+    // `const fn` loops and branches, for `unicase!`
+    println!("cargo:rustc-check-cfg=cfg(__unicase__const_literal)");
+    if ac.probe_rustc_version(1, 46) {
+        println!("cargo:rustc-cfg=__unicase__const_literal");
+    }
+
+    // `const fn` loops and panics, for `const_fold_hash` and the layout
+    // checks in no_opt.rs
+    println!("cargo:rustc-check-cfg=cfg(__unicase__const_fold_hash)");
+    if ac.probe_rustc_version(1, 57) {
+        println!("cargo:rustc-cfg=__unicase__const_fold_hash");
+    }
+
+    // `Hasher::write_str`, so string-aware hashers can take the folded form
+    // in one call. It is still unstable, so on nightly it is only used if the
+    // `nightly` feature asks for it.
+    println!("cargo:rustc-check-cfg=cfg(__unicase__write_str)");
+    println!("cargo:rustc-check-cfg=cfg(__unicase__write_str_unstable)");
+    let probe = "pub fn probe(h: &mut dyn core::hash::Hasher) { h.write_str(\"\") }";
+    if ac.probe_raw(probe).is_ok() {
+        println!("cargo:rustc-cfg=__unicase__write_str");
+    } else if env::var_os("CARGO_FEATURE_NIGHTLY").is_some()
+        && ac
+            .probe_raw(&format!("#![feature(hasher_prefixfree_extras)]\n{}", probe))
+            .is_ok()
+    {
+        println!("cargo:rustc-cfg=__unicase__write_str");
+        println!("cargo:rustc-cfg=__unicase__write_str_unstable");
+    }
+
+    autocfg::rerun_path("build.rs");
+}
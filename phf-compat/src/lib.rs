@@ -0,0 +1 @@
+//! Only hosts the tests in `tests/`.
@@ -0,0 +1,48 @@
+//! Checks that `phf` maps keyed on `UniCase` work.
+//!
+//! `phf` hashes the keys of a map at compile time, in its proc macro, and
+//! the lookup key at runtime. Both go through the `Hash` impls of this
+//! crate, so the hash stream they produce has to be the same in both
+//! places. Changing it between releases broke crates that had generated
+//! maps checked in.
+
+use phf::phf_map;
+use unicase::UniCase;
+
+static HEADERS: phf::Map<UniCase<&'static str>, u16> = phf_map! {
+    UniCase::ascii("Accept") => 1,
+    UniCase::ascii("Content-Type") => 2,
+    UniCase::ascii("Host") => 3,
+    UniCase::unicode("Straße") => 4,
+};
+
+fn lookup(key: &str) -> Option<u16> {
+    HEADERS.get(&UniCase::new(key)).cloned()
+}
+
+#[test]
+fn lookup_ignores_case() {
+    assert_eq!(lookup("accept"), Some(1));
+    assert_eq!(lookup("ACCEPT"), Some(1));
+    assert_eq!(lookup("content-type"), Some(2));
+    assert_eq!(lookup("CoNtEnT-tYpE"), Some(2));
+    assert_eq!(lookup("host"), Some(3));
+    assert_eq!(lookup("Content-Length"), None);
+    assert_eq!(lookup(""), None);
+}
+
+#[test]
+fn lookup_unicode_keys() {
+    assert_eq!(lookup("straße"), Some(4));
+    assert_eq!(lookup("STRASSE"), Some(4));
+    assert_eq!(lookup("strasse"), Some(4));
+}
+
+#[test]
+fn lookup_with_non_static_key() {
+    let owned = String::from("HOST");
+    assert_eq!(HEADERS.get(&UniCase::new(owned.as_str())), Some(&3));
+
+    let entry = HEADERS.get_entry(&UniCase::new("content-TYPE")).unwrap();
+    assert_eq!(entry.0.as_ref(), "Content-Type");
+}
@@ -140,6 +140,8 @@ for high_byte in range(0, 0x2D):
 rs.write('// Generated by scripts/mapgen.py\n')
 rs.write('// %s\n' % datetime.date.today())
 rs.write('\n')
+rs.write('// Ranges are matched without range patterns to stay compatible with old rustcs.\n')
+rs.write('#![allow(clippy::manual_range_contains, clippy::redundant_pattern)]\n\n')
 rs.write('use super::fold::Fold;\n\n')
 rs.write('use core::char;\n');
 rs.write("pub fn lookup(orig: char) -> Fold {\n")
@@ -201,8 +203,7 @@ rs.write('        if let Some(c) = char::from_u32(c_index) {\n');
 rs.write('            let reference: Vec<char> = lookup_naive(c).collect();\n')
 rs.write('            let actual: Vec<char> = lookup(c).collect();\n')
 rs.write('            if actual != reference {\n')
-rs.write('                assert!(\n')
-rs.write('                    false,\n')
+rs.write('                panic!(\n')
 rs.write('                    "case-folding {:?} (#0x{:04x}) failed: Expected {:?}, got {:?}",\n')
 rs.write('                    c, c_index, reference, actual\n')
 rs.write('                );\n')
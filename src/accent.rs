@@ -0,0 +1,149 @@
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use super::hash::hash_chars;
+use super::unicode::lookup;
+use super::UniCase;
+
+/// Case and accent insensitive wrapper of strings.
+///
+/// On top of full Unicode case folding, this decomposes the string (NFD)
+/// and drops combining marks, such as accents and diacritics. So
+/// `"café"`, `"CAFE"` and `"cafe\u{301}"` are all equal. A combining mark
+/// is any character of the general category Mark: mostly the nonspacing
+/// marks (`Mn`), including those with a canonical combining class of 0,
+/// like the Thai vowel sign `U+0E31`, but also the spacing (`Mc`) and
+/// enclosing (`Me`) ones.
+///
+/// This is for matching text that users may type without its accents, as
+/// in a search box, rather than for keys that must stay distinct:
+/// `"resume"` and `"résumé"` become the same. An `AccentInsensitive` only
+/// compares with other `AccentInsensitive`s.
+///
+/// Only available with the `unicode-normalization` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use unicase::AccentInsensitive;
+///
+/// let a = AccentInsensitive::new("Café");
+/// let b = AccentInsensitive::new("CAFE");
+///
+/// assert_eq!(a, b);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AccentInsensitive<S>(S);
+
+impl<S> AccentInsensitive<S> {
+    /// Creates a new `AccentInsensitive`.
+    #[inline]
+    pub const fn new(s: S) -> AccentInsensitive<S> {
+        AccentInsensitive(s)
+    }
+
+    /// Unwraps the inner value.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+impl<S> UniCase<S> {
+    /// Creates a case and accent insensitive string.
+    ///
+    /// Shorthand for [`AccentInsensitive::new`]. `UniCase` itself keeps
+    /// accents significant, so the result is not a `UniCase`.
+    ///
+    /// Only available with the `unicode-normalization` feature.
+    #[inline]
+    pub const fn unicode_accent_insensitive(s: S) -> AccentInsensitive<S> {
+        AccentInsensitive(s)
+    }
+}
+
+impl<S: AsRef<str>> AccentInsensitive<S> {
+    fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.0
+            .as_ref()
+            .nfd()
+            .flat_map(lookup)
+            .filter(|&c| !is_combining_mark(c))
+    }
+}
+
+impl<S: AsRef<str>> AsRef<str> for AccentInsensitive<S> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+impl<S: fmt::Display> fmt::Display for AccentInsensitive<S> {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, fmt)
+    }
+}
+
+impl<S1: AsRef<str>, S2: AsRef<str>> PartialEq<AccentInsensitive<S2>> for AccentInsensitive<S1> {
+    #[inline]
+    fn eq(&self, other: &AccentInsensitive<S2>) -> bool {
+        self.chars().eq(other.chars())
+    }
+}
+
+impl<S: AsRef<str>> Eq for AccentInsensitive<S> {}
+
+impl<S: AsRef<str>> Hash for AccentInsensitive<S> {
+    #[inline]
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        hash_chars(self.chars(), hasher)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AccentInsensitive;
+    use crate::hash::hash_of;
+    use crate::UniCase;
+
+    #[test]
+    fn test_accent_insensitive() {
+        let a = UniCase::unicode_accent_insensitive("café");
+        let b = AccentInsensitive::new("CAFE");
+        let c = AccentInsensitive::new("cafe\u{301}");
+
+        assert_eq!(a, b);
+        assert_eq!(a, c);
+        assert_eq!(hash_of(&a), hash_of(&b));
+        assert_eq!(hash_of(&a), hash_of(&c));
+
+        assert_eq!(
+            AccentInsensitive::new("Maße"),
+            AccentInsensitive::new("MASSE")
+        );
+        assert_eq!(
+            AccentInsensitive::new("Ἀθῆναι"),
+            AccentInsensitive::new("αθηναι")
+        );
+        assert!(AccentInsensitive::new("cafe") != AccentInsensitive::new("cafes"));
+    }
+
+    #[test]
+    fn test_accent_insensitive_marks_without_class() {
+        // U+0E31 THAI CHARACTER MAI HAN-AKAT is `Mn`, with combining class 0
+        let a = AccentInsensitive::new("\u{E01}\u{E31}\u{E19}");
+        let b = AccentInsensitive::new("\u{E01}\u{E19}");
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+        // as is U+180B MONGOLIAN FREE VARIATION SELECTOR ONE
+        assert_eq!(
+            AccentInsensitive::new("\u{1820}\u{180B}"),
+            AccentInsensitive::new("\u{1820}")
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use alloc::string::String;
+
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use super::{Ascii, UniCase};
+
+macro_rules! arbitrary_impl {
+    ($wrapper:ident) => {
+        impl<'a> Arbitrary<'a> for $wrapper<String> {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                String::arbitrary(u).map($wrapper::new)
+            }
+
+            fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+                String::arbitrary_take_rest(u).map($wrapper::new)
+            }
+
+            #[inline]
+            fn size_hint(depth: usize) -> (usize, Option<usize>) {
+                String::size_hint(depth)
+            }
+        }
+    };
+}
+
+arbitrary_impl!(UniCase);
+arbitrary_impl!(Ascii);
+
+#[cfg(test)]
+mod tests {
+    use crate::{Ascii, UniCase};
+    use arbitrary::{Arbitrary, Unstructured};
+    use std::string::String;
+
+    #[test]
+    fn test_unicase_arbitrary() {
+        let bytes = "Maße".as_bytes();
+        let a = UniCase::<String>::arbitrary_take_rest(Unstructured::new(bytes)).unwrap();
+        assert_eq!(a.as_ref(), "Maße");
+        assert!(!a.is_ascii());
+
+        let a = UniCase::<String>::arbitrary_take_rest(Unstructured::new(b"FooBar")).unwrap();
+        assert!(a.is_ascii());
+        assert_eq!(a, UniCase::new("foobar"));
+    }
+
+    #[test]
+    fn test_ascii_arbitrary() {
+        let bytes = b"\x06FooBar and the rest";
+        let expected = String::arbitrary(&mut Unstructured::new(bytes)).unwrap();
+        let a = Ascii::<String>::arbitrary(&mut Unstructured::new(bytes)).unwrap();
+        assert_eq!(a.as_ref(), expected);
+        assert_eq!(a, expected.to_ascii_uppercase());
+    }
+}
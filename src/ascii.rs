@@ -1,3 +1,4 @@
+use alloc::boxed::Box;
 use alloc::string::String;
 use core::cmp::Ordering;
 use core::fmt;
@@ -5,11 +6,18 @@ use core::hash::{Hash, Hasher};
 use core::ops::{Deref, DerefMut};
 use core::str::FromStr;
 
+use super::hash::fold_hash;
 use super::{Ascii, Encoding, UniCase};
 
 impl<S> Ascii<S> {
     /// Construct a new `Ascii`.
     ///
+    /// This only wraps `s`: the string is never scanned or copied, so it
+    /// costs the same no matter how long the input is. That makes it the
+    /// cheap choice when building many values whose encoding is known ahead
+    /// of time. Compare [`UniCase::new`], which checks every byte to pick a
+    /// folding mode.
+    ///
     /// For Rust versions >= 1.31, this is a `const fn`.
     #[inline]
     pub const fn new(s: S) -> Ascii<S> {
@@ -28,17 +36,89 @@ impl<S> Ascii<S> {
     }
 }
 
+#[cfg(__unicase__const_literal)]
+impl Ascii<&str> {
+    /// Compares with another `Ascii<&str>`, ignoring ASCII case, in a
+    /// `const` context.
+    ///
+    /// This gives the same answer as `==`, see [`eq_ascii_const`](crate::eq_ascii_const).
+    ///
+    /// Requires Rust 1.46 or newer.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::Ascii;
+    ///
+    /// const HOST: Ascii<&str> = Ascii::new("Host");
+    /// const SAME: bool = HOST.eq_const(&Ascii::new("HOST"));
+    /// assert!(SAME);
+    /// ```
+    #[inline]
+    pub const fn eq_const(&self, other: &Ascii<&str>) -> bool {
+        super::eq_ascii_const(self.0, other.0)
+    }
+}
+
+impl<S: Into<Box<str>>> Ascii<S> {
+    /// Converts the inner string into a `Box<str>`, keeping its original
+    /// casing.
+    #[inline]
+    pub fn into_boxed_str(self) -> Box<str> {
+        self.0.into()
+    }
+}
+
+impl<S: AsRef<str>> Ascii<S> {
+    str_methods!();
+
+    /// Returns the ASCII-lowercased form of this string as a `Box<str>`.
+    #[inline]
+    pub fn into_folded_boxed_str(self) -> Box<str> {
+        self.0.as_ref().to_ascii_lowercase().into_boxed_str()
+    }
+}
+
+impl<S: AsRef<str>> Ascii<S> {
+    /// Compares against a single char, ignoring ASCII case.
+    ///
+    /// This is `true` only if the string is exactly one char.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::Ascii;
+    ///
+    /// assert!(Ascii::new("K").eq_char('k'));
+    /// assert!(!Ascii::new("Ka").eq_char('k'));
+    /// ```
+    #[inline]
+    pub fn eq_char(&self, c: char) -> bool {
+        let mut chars = self.0.as_ref().chars();
+        match (chars.next(), chars.next()) {
+            (Some(x), None) => x.eq_ignore_ascii_case(&c),
+            _ => false,
+        }
+    }
+}
+
+impl<'a> From<Ascii<&'a str>> for Ascii<String> {
+    fn from(s: Ascii<&'a str>) -> Self {
+        Ascii(String::from(s.0))
+    }
+}
+
 impl<S> Deref for Ascii<S> {
     type Target = S;
     #[inline]
-    fn deref<'a>(&'a self) -> &'a S {
+    fn deref(&self) -> &S {
         &self.0
     }
 }
 
 impl<S> DerefMut for Ascii<S> {
     #[inline]
-    fn deref_mut<'a>(&'a mut self) -> &'a mut S {
+    fn deref_mut(&mut self) -> &mut S {
         &mut self.0
     }
 }
@@ -80,7 +160,7 @@ impl<S1: AsRef<str>> PartialEq<Ascii<S1>> for String {
     }
 }
 
-impl<'a, S1: AsRef<str>> PartialEq<Ascii<S1>> for &'a str {
+impl<S1: AsRef<str>> PartialEq<Ascii<S1>> for &str {
     #[inline]
     fn eq(&self, other: &Ascii<S1>) -> bool {
         other == self
@@ -94,6 +174,36 @@ impl<S1: AsRef<str>, S2: AsRef<str>> PartialEq<S2> for Ascii<S1> {
     }
 }
 
+/// Compares against raw bytes, ignoring ASCII case.
+///
+/// Non-ASCII bytes are compared verbatim against the UTF-8 bytes of the
+/// string. There is no `PartialEq<&[u8]>`, since it would overlap with the
+/// `AsRef<str>` impl, so compare against a slice with `ascii == bytes[..]`.
+impl<S: AsRef<str>> PartialEq<[u8]> for Ascii<S> {
+    #[inline]
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_ref().as_bytes().eq_ignore_ascii_case(other)
+    }
+}
+
+impl<S: AsRef<str>> PartialEq<Ascii<S>> for [u8] {
+    #[inline]
+    fn eq(&self, other: &Ascii<S>) -> bool {
+        other == self
+    }
+}
+
+/// Compares against a single char, see [`Ascii::eq_char`].
+///
+/// There is no `PartialEq<char>` for `Ascii`, since it would overlap with
+/// the `AsRef<str>` impl, so only `char == ascii` works as an operator.
+impl<S: AsRef<str>> PartialEq<Ascii<S>> for char {
+    #[inline]
+    fn eq(&self, other: &Ascii<S>) -> bool {
+        other.eq_char(*self)
+    }
+}
+
 impl<S: AsRef<str>> Eq for Ascii<S> {}
 
 impl<S: FromStr> FromStr for Ascii<S> {
@@ -106,34 +216,25 @@ impl<S: FromStr> FromStr for Ascii<S> {
 impl<S: AsRef<str>> Hash for Ascii<S> {
     #[inline]
     fn hash<H: Hasher>(&self, hasher: &mut H) {
-        for byte in self.as_ref().bytes().map(|b| b.to_ascii_lowercase()) {
-            hasher.write_u8(byte);
-        }
-        // prefix-freedom
-        hasher.write_u8(0xFF);
+        // the same stream as `UniCase`, even for non-ASCII text, since an
+        // ASCII-mode `UniCase` can equal a Unicode-mode one
+        fold_hash(self.as_ref(), hasher)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Ascii;
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
+    use crate::hash::hash_of;
     use std::string::String;
 
-    fn hash<T: Hash>(t: &T) -> u64 {
-        let mut s = DefaultHasher::new();
-        t.hash(&mut s);
-        s.finish()
-    }
-
     #[test]
     fn test_case_insensitive() {
         let a = Ascii("foobar");
         let b = Ascii("FOOBAR");
 
         assert_eq!(a, b);
-        assert_eq!(hash(&a), hash(&b));
+        assert_eq!(hash_of(&a), hash_of(&b));
 
         assert_eq!(a, "fooBar");
         assert_eq!("fooBar", a);
@@ -141,6 +242,48 @@ mod tests {
         assert_eq!(a, String::from("fooBar"));
     }
 
+    #[test]
+    fn test_new_does_not_scan() {
+        // no `AsRef<str>` bound, so there is nothing `new` could scan
+        struct Opaque;
+        let _ = Ascii::new(Opaque);
+
+        const LONG: Ascii<&str> = Ascii::new("not checked at all: ß");
+        assert_eq!(LONG.into_inner(), "not checked at all: ß");
+    }
+
+    #[cfg(feature = "nightly")]
+    #[bench]
+    fn bench_ascii_new_short(b: &mut ::test::Bencher) {
+        let s = "foobar";
+        b.iter(|| Ascii::new(::test::black_box(s)));
+    }
+
+    #[cfg(feature = "nightly")]
+    #[bench]
+    fn bench_ascii_new_long(b: &mut ::test::Bencher) {
+        let s: String = "foobar".repeat(10_000);
+        b.iter(|| Ascii::new(::test::black_box(s.as_str())));
+    }
+
+    #[test]
+    fn test_eq_bytes() {
+        let a = Ascii::new("Content-Type");
+        assert!(a == b"content-type"[..]);
+        assert!(b"CONTENT-TYPE"[..] == a);
+        assert!(a != b"content-length"[..]);
+        assert!(a != b"content-type "[..]);
+
+        let bytes: &[u8] = b"content-type";
+        assert!(a == *bytes);
+
+        // non-ASCII bytes are compared as they are
+        let b = Ascii::new("Caf\u{e9}");
+        assert!(b == b"CAF\xC3\xA9"[..]);
+        assert!(b != b"CAF\xC3\x89"[..]);
+        assert!(b != b"cafe"[..]);
+    }
+
     #[cfg(feature = "nightly")]
     #[bench]
     fn bench_ascii_eq(b: &mut ::test::Bencher) {
@@ -148,6 +291,17 @@ mod tests {
         b.iter(|| assert_eq!(Ascii("foobar"), Ascii("FOOBAR")));
     }
 
+    #[test]
+    fn test_eq_char() {
+        assert!(Ascii::new("K").eq_char('k'));
+        assert!('K' == Ascii::new("k"));
+        assert!(Ascii::new("Ä").eq_char('Ä'));
+        assert!(!Ascii::new("Ä").eq_char('ä'));
+        assert!('k' != Ascii::new("\u{212A}"));
+        assert!(!Ascii::new("kk").eq_char('k'));
+        assert!(!Ascii::new("").eq_char('k'));
+    }
+
     #[test]
     fn test_case_cmp() {
         assert!(Ascii("foobar") == Ascii("FOOBAR"));
@@ -160,6 +314,20 @@ mod tests {
         assert!(Ascii("a") < Ascii("AA"));
     }
 
+    #[test]
+    fn test_into_boxed_str() {
+        let a = Ascii::new(String::from("Content-Type"));
+        assert_eq!(&*a.clone().into_boxed_str(), "Content-Type");
+        assert_eq!(&*a.into_folded_boxed_str(), "content-type");
+        assert_eq!(&*Ascii::new("ÄB").into_folded_boxed_str(), "Äb");
+    }
+
+    #[test]
+    fn test_from_borrowed() {
+        let a: Ascii<String> = Ascii::new("FooBar").into();
+        assert_eq!(a.into_inner(), "FooBar");
+    }
+
     #[test]
     fn test_ascii_new_const() {
         const _ASCII: Ascii<&'static str> = Ascii::new("");
@@ -0,0 +1,179 @@
+use core::array;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::Take;
+
+use ::bstr::{BStr, ByteSlice};
+
+use super::hash::{hash_bytes, utf8_bytes};
+use super::unicode::lookup;
+
+/// Case-insensitive wrapper of byte strings that are usually, but not
+/// necessarily, UTF-8.
+///
+/// Valid UTF-8 runs are compared with full Unicode case folding, exactly
+/// like [`UniCase`](crate::UniCase). ASCII is folded the same in either
+/// mode, so pure ASCII bytes behave as they would in `Ascii`. Bytes that are
+/// not part of valid UTF-8 are kept as they are: they only equal the same
+/// bytes, rather than all collapsing into `U+FFFD` as a lossy conversion
+/// would, which keeps `Eq` and `Hash` consistent.
+///
+/// For valid UTF-8, ordering and hashing also agree with `UniCase`.
+///
+/// Works with anything that is `AsRef<BStr>`, such as `&BStr` and `BString`.
+///
+/// Only available with the `bstr` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use bstr::{BStr, BString};
+/// use unicase::UniCaseBStr;
+///
+/// let a = UniCaseBStr::new(BString::from(&b"Stra\xC3\x9Fe \xFF"[..]));
+/// let b = UniCaseBStr::new(BStr::new(b"STRASSE \xFF"));
+///
+/// assert_eq!(a, b);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UniCaseBStr<B>(B);
+
+/// One unit of the folded stream: a folded char, or a byte that wasn't
+/// valid UTF-8.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Unit {
+    Char(char),
+    Byte(u8),
+}
+
+impl Unit {
+    /// The bytes this unit hashes as: UTF-8 for a char, or the byte itself.
+    fn bytes(self) -> Take<array::IntoIter<u8, 4>> {
+        match self {
+            Unit::Char(c) => utf8_bytes(c),
+            Unit::Byte(b) => IntoIterator::into_iter([b, 0, 0, 0]).take(1),
+        }
+    }
+}
+
+impl<B> UniCaseBStr<B> {
+    /// Creates a new `UniCaseBStr`.
+    #[inline]
+    pub const fn new(b: B) -> UniCaseBStr<B> {
+        UniCaseBStr(b)
+    }
+
+    /// Unwraps the inner value.
+    #[inline]
+    pub fn into_inner(self) -> B {
+        self.0
+    }
+}
+
+impl<B: AsRef<BStr>> UniCaseBStr<B> {
+    fn units(&self) -> impl Iterator<Item = Unit> + '_ {
+        // `bstr`'s, rather than the newer inherent `[u8]::utf8_chunks`
+        ByteSlice::utf8_chunks(&**self.0.as_ref()).flat_map(|chunk| {
+            chunk
+                .valid()
+                .chars()
+                .flat_map(lookup)
+                .map(Unit::Char)
+                .chain(chunk.invalid().iter().map(|&b| Unit::Byte(b)))
+        })
+    }
+}
+
+impl<B: AsRef<BStr>> AsRef<BStr> for UniCaseBStr<B> {
+    #[inline]
+    fn as_ref(&self) -> &BStr {
+        self.0.as_ref()
+    }
+}
+
+impl<B: fmt::Display> fmt::Display for UniCaseBStr<B> {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, fmt)
+    }
+}
+
+impl<B1: AsRef<BStr>, B2: AsRef<BStr>> PartialEq<UniCaseBStr<B2>> for UniCaseBStr<B1> {
+    #[inline]
+    fn eq(&self, other: &UniCaseBStr<B2>) -> bool {
+        self.units().eq(other.units())
+    }
+}
+
+impl<B: AsRef<BStr>> Eq for UniCaseBStr<B> {}
+
+impl<B: AsRef<BStr>> PartialOrd for UniCaseBStr<B> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<B: AsRef<BStr>> Ord for UniCaseBStr<B> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.units().cmp(other.units())
+    }
+}
+
+impl<B: AsRef<BStr>> Hash for UniCaseBStr<B> {
+    #[inline]
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        hash_bytes(self.units().flat_map(Unit::bytes), hasher)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UniCaseBStr;
+    use crate::hash::hash_of;
+    use crate::UniCase;
+    use bstr::{BStr, BString};
+
+    fn ci(b: &[u8]) -> UniCaseBStr<&BStr> {
+        UniCaseBStr::new(BStr::new(b))
+    }
+
+    #[test]
+    fn test_valid_utf8() {
+        assert_eq!(ci(b"Content-Type"), ci(b"CONTENT-TYPE"));
+        assert_eq!(ci("Maße".as_bytes()), ci(b"MASSE"));
+        assert_eq!(
+            UniCaseBStr::new(BString::from("ΣΊΣΥΦΟΣ")),
+            ci("σίσυφος".as_bytes())
+        );
+        assert!(ci(b"foo") != ci(b"foobar"));
+
+        // agrees with `UniCase` on valid UTF-8
+        for &s in &["Maße", "FooBar", "ΣΊΣΥΦΟΣ", ""] {
+            assert_eq!(hash_of(&ci(s.as_bytes())), hash_of(&UniCase::new(s)));
+        }
+        assert!(ci("Maße".as_bytes()) < ci(b"MAST"));
+        assert!(UniCase::new("Maße") < UniCase::new("MAST"));
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let a = ci(b"ABC\xFFd\xC3");
+        let b = ci(b"abc\xFFD\xC3");
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+
+        // valid runs around invalid bytes are still Unicode folded
+        assert_eq!(ci(b"\xFEStra\xC3\x9Fe"), ci(b"\xFEstrasse"));
+
+        // invalid bytes only match themselves, not each other
+        assert!(ci(b"a\xFF") != ci(b"a\xFE"));
+        assert!(ci(b"a\xFF") != ci("a\u{FFFD}".as_bytes()));
+        assert!(ci(b"a\xFE") < ci(b"a\xFF"));
+
+        // a truncated char is not its complete form
+        assert!(ci(b"\xC3") != ci("\u{C3}".as_bytes()));
+    }
+}
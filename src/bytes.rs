@@ -0,0 +1,150 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+use super::hash::hash_bytes;
+
+/// Case-insensitive wrapper of byte strings, folding only ASCII letters.
+///
+/// This is the byte counterpart of [`Ascii`](crate::Ascii), for keys that
+/// aren't guaranteed to be UTF-8, such as header field names read from raw
+/// bytes. ASCII letters compare without regard to case, and every other
+/// byte only equals itself. `Ord` compares the ASCII-lowercased bytes.
+///
+/// Works with anything that is `AsRef<[u8]>`, such as `&[u8]` and
+/// `Vec<u8>`. With the `serde` feature, an `AsciiBytes<Vec<u8>>`
+/// serializes as bytes, and deserializes from bytes, strings, or sequences
+/// of bytes.
+///
+/// # Example
+///
+/// ```rust
+/// use unicase::AsciiBytes;
+///
+/// let a = AsciiBytes::new(b"Content-Type".to_vec());
+/// let b = AsciiBytes::new(&b"CONTENT-TYPE"[..]);
+///
+/// assert_eq!(a, b);
+/// assert!(AsciiBytes::new(&b"\xFF"[..]) != AsciiBytes::new(&b"\xDF"[..]));
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct AsciiBytes<B>(B);
+
+impl<B> AsciiBytes<B> {
+    /// Creates a new `AsciiBytes`.
+    #[inline]
+    pub const fn new(b: B) -> AsciiBytes<B> {
+        AsciiBytes(b)
+    }
+
+    /// Unwraps the inner value.
+    #[inline]
+    pub fn into_inner(self) -> B {
+        self.0
+    }
+}
+
+impl<B: AsRef<[u8]>> AsciiBytes<B> {
+    fn folded(&self) -> impl Iterator<Item = u8> + '_ {
+        self.0.as_ref().iter().map(u8::to_ascii_lowercase)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for AsciiBytes<B> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+/// Formats like a byte string literal, escaping anything that isn't
+/// printable ASCII.
+impl<B: AsRef<[u8]>> fmt::Debug for AsciiBytes<B> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("b\"")?;
+        for &b in self.0.as_ref() {
+            for e in core::ascii::escape_default(b) {
+                fmt::Write::write_char(fmt, e as char)?;
+            }
+        }
+        fmt.write_str("\"")
+    }
+}
+
+impl<B1: AsRef<[u8]>, B2: AsRef<[u8]>> PartialEq<AsciiBytes<B2>> for AsciiBytes<B1> {
+    #[inline]
+    fn eq(&self, other: &AsciiBytes<B2>) -> bool {
+        self.0.as_ref().eq_ignore_ascii_case(other.0.as_ref())
+    }
+}
+
+impl<B: AsRef<[u8]>> Eq for AsciiBytes<B> {}
+
+impl<B: AsRef<[u8]>> PartialOrd for AsciiBytes<B> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<B: AsRef<[u8]>> Ord for AsciiBytes<B> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.folded().cmp(other.folded())
+    }
+}
+
+impl<B: AsRef<[u8]>> Hash for AsciiBytes<B> {
+    #[inline]
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        hash_bytes(self.folded(), hasher)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AsciiBytes;
+    use crate::hash::hash_of;
+    use crate::Ascii;
+    use std::format;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_ascii_bytes() {
+        let a = AsciiBytes::new(b"Subject\xFF".to_vec());
+        let b = AsciiBytes::new(&b"SUBJECT\xFF"[..]);
+        assert_eq!(a, b);
+        assert_eq!(
+            hash_of(&a),
+            hash_of(&AsciiBytes::new(b"subject\xFF".to_vec()))
+        );
+
+        // only ASCII letters fold
+        assert!(AsciiBytes::new(&b"\xC3\x84"[..]) != AsciiBytes::new("ä".as_bytes()));
+        assert!(AsciiBytes::new(&b"a\xFF"[..]) != AsciiBytes::new(&b"A\xFE"[..]));
+        assert!(AsciiBytes::new(&b"ab"[..]) != AsciiBytes::new(&b"abc"[..]));
+
+        // agrees with `Ascii` on ASCII text
+        assert_eq!(
+            hash_of(&AsciiBytes::new(&b"FooBar"[..])),
+            hash_of(&Ascii::new("foobar"))
+        );
+    }
+
+    #[test]
+    fn test_ascii_bytes_ord() {
+        let mut v: Vec<AsciiBytes<&[u8]>> = [&b"b"[..], b"A", b"\xFF", b"a\x00", b"C"]
+            .iter()
+            .map(|&b| AsciiBytes::new(b))
+            .collect();
+        v.sort();
+        let sorted: Vec<&[u8]> = v.iter().map(|b| b.as_ref()).collect();
+        assert_eq!(sorted, [&b"A"[..], b"a\x00", b"b", b"C", b"\xFF"]);
+    }
+
+    #[test]
+    fn test_ascii_bytes_debug() {
+        let a = AsciiBytes::new(&b"To: \"x\"\xFF"[..]);
+        assert_eq!(format!("{:?}", a), r#"b"To: \"x\"\xff""#);
+    }
+}
@@ -0,0 +1,417 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasher;
+
+use super::{UniCase, UniCaseNoOpt};
+
+/// Extension methods for a `HashSet` of `UniCase<String>` keys.
+///
+/// These take plain `&str` arguments, and look them up through
+/// [`UniCaseNoOpt`] so that only inserting a new value allocates.
+///
+/// Only available with the `std` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashSet;
+/// use unicase::UniCaseSetExt;
+///
+/// let mut set = HashSet::new();
+/// set.insert_str("Foo");
+///
+/// assert!(set.contains_str("FOO"));
+/// assert!(!set.insert_str("foo"));
+/// assert_eq!(set.iter().next().unwrap().as_ref(), "Foo");
+/// ```
+pub trait UniCaseSetExt {
+    /// Adds `s` to the set, unless a case-insensitively equal value is
+    /// already present.
+    ///
+    /// Returns whether the value was newly inserted. An existing entry keeps
+    /// its original casing.
+    fn insert_str(&mut self, s: &str) -> bool;
+
+    /// Returns `true` if the set contains a value case-insensitively equal to
+    /// `s`.
+    fn contains_str(&self, s: &str) -> bool;
+
+    /// Removes the value case-insensitively equal to `s`, returning whether
+    /// it was present.
+    fn remove_str(&mut self, s: &str) -> bool;
+}
+
+impl<H: BuildHasher> UniCaseSetExt for HashSet<UniCase<String>, H> {
+    fn insert_str(&mut self, s: &str) -> bool {
+        if self.contains_str(s) {
+            return false;
+        }
+        self.insert(UniCase::new(s.to_owned()))
+    }
+
+    fn contains_str(&self, s: &str) -> bool {
+        self.contains(UniCaseNoOpt::from_ref(s))
+    }
+
+    fn remove_str(&mut self, s: &str) -> bool {
+        self.remove(UniCaseNoOpt::from_ref(s))
+    }
+}
+
+/// Extension methods for a `HashMap` with `UniCase<String>` keys.
+///
+/// Only available with the `std` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use unicase::UniCaseMapExt;
+///
+/// let mut counts = HashMap::new();
+/// for word in &["Foo", "foo", "FOO"] {
+///     *counts.entry_ci(*word).or_insert(0) += 1;
+/// }
+/// assert_eq!(counts.len(), 1);
+/// assert_eq!(counts.keys().next().unwrap().as_ref(), "Foo");
+/// ```
+pub trait UniCaseMapExt<V> {
+    /// Gets the entry for `key`, matching existing keys case-insensitively.
+    ///
+    /// If no key matches, inserting through a vacant entry stores `key` with
+    /// its casing as given. If one does, the existing key, and its casing,
+    /// is left as is.
+    fn entry_ci(&mut self, key: impl Into<String>) -> Entry<'_, UniCase<String>, V>;
+
+    /// Returns the value for `key`, matching existing keys
+    /// case-insensitively, inserting the result of `f` if there is none.
+    ///
+    /// Unlike [`entry_ci`](UniCaseMapExt::entry_ci), this takes a `&str`
+    /// and looks it up through [`UniCaseNoOpt`], so the owned key is only
+    /// allocated when a value is actually inserted. That suits interning and
+    /// caching, where most lookups hit.
+    ///
+    /// The key is hashed twice either way: once to look it up, then again to
+    /// borrow or insert the value.
+    fn get_or_insert_with_ci<F: FnOnce() -> V>(&mut self, key: &str, f: F) -> &mut V;
+}
+
+impl<V, H: BuildHasher> UniCaseMapExt<V> for HashMap<UniCase<String>, V, H> {
+    fn entry_ci(&mut self, key: impl Into<String>) -> Entry<'_, UniCase<String>, V> {
+        // `HashMap::entry` only keeps the given key when it is vacant
+        self.entry(UniCase::new(key.into()))
+    }
+
+    fn get_or_insert_with_ci<F: FnOnce() -> V>(&mut self, key: &str, f: F) -> &mut V {
+        // returning the `get_mut` borrow from one branch keeps `self`
+        // borrowed in the other, so look the key up first
+        if self.contains_key(UniCaseNoOpt::from_ref(key)) {
+            return self
+                .get_mut(UniCaseNoOpt::from_ref(key))
+                .expect("key was just found");
+        }
+        self.entry(UniCase::new(key.to_owned())).or_insert_with(f)
+    }
+}
+
+/// Removes strings that case-insensitively equal an earlier one, in place.
+///
+/// The first occurrence of each string is kept, with its casing, and the
+/// order of the kept strings is preserved. Nothing is cloned: the strings
+/// are only borrowed to find the duplicates, then removed with
+/// `Vec::retain`.
+///
+/// Only available with the `std` feature.
+///
+/// # Example
+///
+/// ```rust
+/// let mut tags = vec!["Rust".to_string(), "rust".to_string(), "Go".to_string(), "RUST".to_string()];
+/// unicase::retain_unique_ci(&mut tags);
+/// assert_eq!(tags, ["Rust", "Go"]);
+/// ```
+pub fn retain_unique_ci(v: &mut Vec<String>) {
+    let keep: Vec<bool> = {
+        let mut seen = HashSet::with_capacity(v.len());
+        v.iter().map(|s| seen.insert(UniCase::new(&**s))).collect()
+    };
+    let mut keep = keep.into_iter();
+    v.retain(|_| keep.next().unwrap_or(true));
+}
+
+/// Merges `overrides` into `base`, matching keys case-insensitively.
+///
+/// Each value in `overrides` replaces the value of the case-insensitively
+/// equal key in `base`, or is added if there is none. When a key is in
+/// both, `base`'s casing of it is kept, so merging `{"FOO": 2}` into
+/// `{"Foo": 1}` gives `{"Foo": 2}`. Keys only in `overrides` keep their own
+/// casing.
+///
+/// Only available with the `std` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use unicase::UniCase;
+///
+/// let mut config = HashMap::new();
+/// config.insert(UniCase::new("Timeout".to_string()), 30);
+/// let mut overrides = HashMap::new();
+/// overrides.insert(UniCase::new("TIMEOUT".to_string()), 60);
+///
+/// unicase::merge_ci(&mut config, overrides);
+/// let (key, value) = config.iter().next().unwrap();
+/// assert_eq!((key.as_ref(), *value), ("Timeout", 60));
+/// ```
+pub fn merge_ci<V, H1, H2>(
+    base: &mut HashMap<UniCase<String>, V, H1>,
+    overrides: HashMap<UniCase<String>, V, H2>,
+) where
+    H1: BuildHasher,
+{
+    for (key, value) in overrides {
+        match base.entry(key) {
+            // the entry holds on to the key already in the map
+            Entry::Occupied(mut entry) => {
+                entry.insert(value);
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+            }
+        }
+    }
+}
+
+/// Groups values by case-insensitively equal keys.
+///
+/// Each group is keyed by the first casing of its key that was seen, and
+/// holds its values in the order they came in. This is
+/// [`entry_ci`](UniCaseMapExt::entry_ci) in a loop, for the common case of
+/// aggregating records by a key whose casing varies.
+///
+/// Only available with the `std` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use unicase::UniCase;
+///
+/// let groups = unicase::group_by_ci(vec![
+///     ("Foo".to_string(), 1),
+///     ("foo".to_string(), 2),
+///     ("Bar".to_string(), 3),
+/// ]);
+/// assert_eq!(groups.len(), 2);
+/// assert_eq!(groups[&UniCase::new("FOO".to_string())], [1, 2]);
+/// ```
+pub fn group_by_ci<T>(
+    items: impl IntoIterator<Item = (String, T)>,
+) -> HashMap<UniCase<String>, Vec<T>> {
+    let mut groups = HashMap::new();
+    for (key, value) in items {
+        groups.entry_ci(key).or_insert_with(Vec::new).push(value);
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{group_by_ci, merge_ci, retain_unique_ci, UniCaseMapExt, UniCaseSetExt};
+    use crate::UniCase;
+    use std::collections::{HashMap, HashSet};
+    use std::string::{String, ToString};
+    use std::vec::Vec;
+
+    #[test]
+    fn test_set_ext() {
+        let mut set = HashSet::new();
+        assert!(set.insert_str("Foo"));
+        assert!(set.contains_str("FOO"));
+        assert!(set.contains_str("foo"));
+        assert!(!set.contains_str("bar"));
+
+        assert!(!set.insert_str("fOO"));
+        assert_eq!(set.len(), 1);
+        assert_eq!(set.iter().next().unwrap().as_ref(), "Foo");
+
+        assert!(set.remove_str("FOO"));
+        assert!(!set.remove_str("foo"));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_set_ext_unicode() {
+        let mut set = HashSet::new();
+        assert!(set.insert_str("Maße"));
+        assert!(set.contains_str("MASSE"));
+        assert!(set.remove_str("masse"));
+    }
+
+    #[test]
+    fn test_map_entry_ci() {
+        let mut counts = HashMap::new();
+        for &word in &["Foo", "foo", "FOO"] {
+            *counts.entry_ci(word).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 1);
+        let (key, count) = counts.iter().next().unwrap();
+        assert_eq!(key.as_ref(), "Foo");
+        assert_eq!(*count, 3);
+
+        *counts.entry_ci(String::from("Straße")).or_insert(0) += 1;
+        *counts.entry_ci("STRASSE").or_insert(0) += 1;
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[&UniCase::new(String::from("strasse"))], 2);
+    }
+
+    #[test]
+    fn test_get_or_insert_with_ci() {
+        let mut map = HashMap::new();
+        *map.get_or_insert_with_ci("Content-Type", || 0) += 1;
+        *map.get_or_insert_with_ci("CONTENT-TYPE", || panic!("hit")) += 1;
+        *map.get_or_insert_with_ci("Straße", || 10) += 1;
+        *map.get_or_insert_with_ci("STRASSE", || 20) += 1;
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[&UniCase::new(String::from("content-type"))], 2);
+        assert_eq!(map[&UniCase::new(String::from("strasse"))], 12);
+        // the first casing is the one stored
+        let mut keys: Vec<&str> = map.keys().map(|k| k.as_ref()).collect();
+        keys.sort();
+        assert_eq!(keys, ["Content-Type", "Straße"]);
+    }
+
+    #[test]
+    fn test_retain_unique_ci() {
+        let mut v: Vec<String> = ["b", "A", "a", "B", "Maße", "c", "MASSE", "b"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let ptr = v.as_ptr();
+        let first = v[0].as_ptr();
+
+        retain_unique_ci(&mut v);
+        assert_eq!(v, ["b", "A", "Maße", "c"]);
+        // same buffer, and the kept strings were moved rather than cloned
+        assert_eq!(v.as_ptr(), ptr);
+        assert_eq!(v[0].as_ptr(), first);
+
+        let mut empty: Vec<String> = Vec::new();
+        retain_unique_ci(&mut empty);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_merge_ci() {
+        let map = |pairs: &[(&str, i32)]| -> HashMap<UniCase<String>, i32> {
+            pairs
+                .iter()
+                .map(|&(k, v)| (UniCase::new(k.to_string()), v))
+                .collect()
+        };
+
+        let mut base = map(&[("Foo", 1)]);
+        merge_ci(&mut base, map(&[("FOO", 2)]));
+        assert_eq!(base.len(), 1);
+        let (key, value) = base.iter().next().unwrap();
+        assert_eq!((key.as_ref(), *value), ("Foo", 2));
+
+        let mut base = map(&[("Foo", 1), ("Straße", 2), ("keep", 3)]);
+        merge_ci(&mut base, map(&[("STRASSE", 20), ("New", 4)]));
+        let mut merged: Vec<(&str, i32)> = base.iter().map(|(k, &v)| (k.as_ref(), v)).collect();
+        merged.sort();
+        assert_eq!(
+            merged,
+            [("Foo", 1), ("New", 4), ("Straße", 20), ("keep", 3)]
+        );
+    }
+
+    #[test]
+    fn test_group_by_ci() {
+        let items = [("Foo", 1), ("foo", 2), ("Bar", 3)]
+            .iter()
+            .map(|&(k, v)| (k.to_string(), v));
+        let groups = group_by_ci(items);
+        assert_eq!(groups.len(), 2);
+
+        let mut groups: Vec<(&str, &[i32])> =
+            groups.iter().map(|(k, v)| (k.as_ref(), &v[..])).collect();
+        groups.sort();
+        assert_eq!(groups, [("Bar", &[3][..]), ("Foo", &[1, 2][..])]);
+
+        let unicode = group_by_ci(
+            ["MASSE", "Maße", "masse"]
+                .iter()
+                .enumerate()
+                .map(|(i, k)| (k.to_string(), i)),
+        );
+        let (key, values) = unicode.iter().next().unwrap();
+        assert_eq!(key.as_ref(), "MASSE");
+        assert_eq!(*values, [0, 1, 2]);
+    }
+
+    #[cfg(feature = "nightly")]
+    fn bench_keys() -> (HashMap<UniCase<String>, usize>, Vec<String>, Vec<String>) {
+        let mut map = HashMap::new();
+        let mut hits = Vec::new();
+        let mut misses = Vec::new();
+        for i in 0..1000 {
+            map.insert(UniCase::new(std::format!("X-Header-{}", i)), i);
+            hits.push(std::format!("x-HEADER-{}", i));
+            misses.push(std::format!("x-other-{}", i));
+        }
+        (map, hits, misses)
+    }
+
+    #[cfg(feature = "nightly")]
+    #[bench]
+    fn bench_get_or_insert_with_ci_hit(b: &mut ::test::Bencher) {
+        let (mut map, hits, _) = bench_keys();
+        b.iter(|| {
+            for key in &hits {
+                *map.get_or_insert_with_ci(key, || 0) += 1;
+            }
+        });
+    }
+
+    #[cfg(feature = "nightly")]
+    #[bench]
+    fn bench_entry_ci_hit(b: &mut ::test::Bencher) {
+        let (mut map, hits, _) = bench_keys();
+        b.iter(|| {
+            for key in &hits {
+                *map.entry_ci(&**key).or_insert(0) += 1;
+            }
+        });
+    }
+
+    #[cfg(feature = "nightly")]
+    #[bench]
+    fn bench_get_or_insert_with_ci_miss(b: &mut ::test::Bencher) {
+        let (map, _, misses) = bench_keys();
+        b.iter(|| {
+            let mut map = map.clone();
+            for key in &misses {
+                *map.get_or_insert_with_ci(key, || 0) += 1;
+            }
+            map
+        });
+    }
+
+    #[cfg(feature = "nightly")]
+    #[bench]
+    fn bench_entry_ci_miss(b: &mut ::test::Bencher) {
+        let (map, _, misses) = bench_keys();
+        b.iter(|| {
+            let mut map = map.clone();
+            for key in &misses {
+                *map.entry_ci(&**key).or_insert(0) += 1;
+            }
+            map
+        });
+    }
+}
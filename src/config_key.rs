@@ -0,0 +1,134 @@
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+use super::hash::hash_chars;
+use super::unicode::lookup;
+use super::UniCase;
+
+/// Case insensitive wrapper of configuration keys, which also treats `-`
+/// and `_` as the same separator.
+///
+/// On top of full Unicode case folding, every `_` (U+005F LOW LINE) is
+/// compared as `-` (U+002D HYPHEN-MINUS). Those two are the only extra
+/// equivalence: other dashes, such as `‐` (U+2010) or `−` (U+2212), and
+/// other separators, such as `.` and spaces, are compared as usual, and
+/// runs of separators are not collapsed. So `"Max-Size"` equals
+/// `"max_size"`, but not `"max__size"`, `"max.size"` or `"maxsize"`.
+///
+/// This suits settings gathered from several sources with their own
+/// conventions, such as `max_size` in a file and `MAX-SIZE` from the
+/// environment, which should land on one map entry. A `ConfigKey` only
+/// compares with other `ConfigKey`s.
+///
+/// # Example
+///
+/// ```rust
+/// use unicase::{ConfigKey, UniCase};
+///
+/// let a = ConfigKey::new("Max-Size");
+/// let b = ConfigKey::new("max_size");
+///
+/// assert_eq!(a, b);
+/// assert_ne!(UniCase::new("Max-Size"), UniCase::new("max_size"));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ConfigKey<S>(S);
+
+impl<S> ConfigKey<S> {
+    /// Creates a new `ConfigKey`.
+    #[inline]
+    pub const fn new(s: S) -> ConfigKey<S> {
+        ConfigKey(s)
+    }
+
+    /// Unwraps the inner value.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+impl<S> UniCase<S> {
+    /// Creates a case insensitive configuration key, which also treats `-`
+    /// and `_` as equal.
+    ///
+    /// Shorthand for [`ConfigKey::new`]. `UniCase` itself keeps `-` and `_`
+    /// apart, so the result is not a `UniCase`.
+    #[inline]
+    pub const fn config_key(s: S) -> ConfigKey<S> {
+        ConfigKey(s)
+    }
+}
+
+impl<S: AsRef<str>> ConfigKey<S> {
+    fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        // nothing else folds to `_` or `-`, so mapping after folding is enough
+        self.0
+            .as_ref()
+            .chars()
+            .flat_map(lookup)
+            .map(|c| if c == '_' { '-' } else { c })
+    }
+}
+
+impl<S: AsRef<str>> AsRef<str> for ConfigKey<S> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+impl<S: fmt::Display> fmt::Display for ConfigKey<S> {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, fmt)
+    }
+}
+
+impl<S1: AsRef<str>, S2: AsRef<str>> PartialEq<ConfigKey<S2>> for ConfigKey<S1> {
+    #[inline]
+    fn eq(&self, other: &ConfigKey<S2>) -> bool {
+        self.chars().eq(other.chars())
+    }
+}
+
+impl<S: AsRef<str>> Eq for ConfigKey<S> {}
+
+impl<S: AsRef<str>> Hash for ConfigKey<S> {
+    #[inline]
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        hash_chars(self.chars(), hasher)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConfigKey;
+    use crate::hash::hash_of;
+    use crate::UniCase;
+
+    #[test]
+    fn test_config_key_separators() {
+        let a = UniCase::config_key("Max-Size");
+        let b = ConfigKey::new("max_size");
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+
+        for &s in &["MAX_SIZE", "max-size", "Max_Size", "max-SIZE"] {
+            assert_eq!(ConfigKey::new(s), a, "{:?}", s);
+            assert_eq!(hash_of(&ConfigKey::new(s)), hash_of(&a), "{:?}", s);
+        }
+        assert_eq!(ConfigKey::new("_Straße-"), ConfigKey::new("-strasse_"));
+
+        // only `-` and `_`, one for one
+        for &s in &[
+            "maxsize",
+            "max__size",
+            "max.size",
+            "max size",
+            "max\u{2010}size",
+        ] {
+            assert!(ConfigKey::new(s) != a, "{:?}", s);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use unicode_security::confusable_detection::skeleton;
+
+use super::unicode::lookup;
+use super::UniCase;
+
+/// Returns `true` if `a` and `b` look alike, ignoring case.
+///
+/// Both strings are case-folded, reduced to their confusable "skeleton" as
+/// defined by [UTS #39](https://www.unicode.org/reports/tr39/#Confusable_Detection),
+/// and folded again, then compared. The skeleton maps characters that are
+/// easily mistaken for each other to one prototype, so the Cyrillic `"аdmin"`
+/// looks like the Latin `"ADMIN"` here, while `UniCase` tells them apart.
+///
+/// Use it to flag a new name that looks like an existing one without being
+/// equal to it, such as when registering usernames. It is a heuristic, not a
+/// guarantee: the confusables data only covers characters that are
+/// confusable in common fonts, and skeletons are meant for comparison only,
+/// never for display or storage.
+///
+/// Only available with the `confusables` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use unicase::UniCase;
+///
+/// let latin = "admin";
+/// let cyrillic = "\u{430}dmin";
+///
+/// assert!(unicase::looks_like(latin, cyrillic));
+/// assert!(UniCase::new(latin) != UniCase::new(cyrillic));
+/// ```
+pub fn looks_like(a: &str, b: &str) -> bool {
+    let a = UniCase::new(a).to_folded_case();
+    let b = UniCase::new(b).to_folded_case();
+    refolded_skeleton(&a).eq(refolded_skeleton(&b))
+}
+
+/// The prototypes can be capitals, such as for some digits and symbols, so
+/// the skeleton is folded again.
+fn refolded_skeleton(folded: &str) -> impl Iterator<Item = char> + '_ {
+    skeleton(folded).flat_map(lookup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::looks_like;
+    use crate::UniCase;
+
+    #[test]
+    fn test_cyrillic_lookalike() {
+        let latin = "admin";
+        let cyrillic = "\u{430}dmin";
+        assert!(UniCase::new(latin) != UniCase::new(cyrillic));
+        assert!(looks_like(latin, cyrillic));
+        assert!(looks_like("ADMIN", cyrillic));
+        assert!(looks_like("\u{410}DMIN", "admin"));
+        assert!(!looks_like("admin", "\u{430}dmin1"));
+    }
+
+    #[test]
+    fn test_looks_like() {
+        // anything equal under `UniCase` also looks alike
+        assert!(looks_like("Maße", "MASSE"));
+        assert!(looks_like("paypal", "PayPal"));
+        // Latin `l` and digit `1`, and Greek omicron and Latin `o`
+        assert!(looks_like("paypal", "paypa1"));
+        assert!(looks_like("g\u{3BF}\u{3BF}gle", "GOOGLE"));
+        assert!(!looks_like("google", "goggle"));
+        assert!(!looks_like("", "a"));
+    }
+}
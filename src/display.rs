@@ -0,0 +1,151 @@
+use core::fmt::{self, Write};
+
+use super::{FoldedChars, UniCase};
+
+/// Displays a `UniCase` as it was written.
+///
+/// Returned by [`UniCase::display_original`].
+pub struct DisplayOriginal<'a, S>(&'a UniCase<S>);
+
+/// Displays the case-folded form of a `UniCase`, without allocating.
+///
+/// Returned by [`UniCase::display_folded`].
+pub struct DisplayFolded<'a> {
+    s: &'a str,
+    ascii: bool,
+}
+
+impl<S> UniCase<S> {
+    /// Returns an adapter that displays the original string.
+    ///
+    /// This is the same as the `Display` impl of `UniCase` itself, for
+    /// symmetry with [`display_folded`](UniCase::display_folded).
+    #[inline]
+    pub fn display_original(&self) -> DisplayOriginal<'_, S> {
+        DisplayOriginal(self)
+    }
+}
+
+impl<S: AsRef<str>> UniCase<S> {
+    /// Returns an adapter that displays the case-folded string.
+    ///
+    /// The folded chars are written straight into the formatter, so this
+    /// gives the same output as [`to_folded_case`](UniCase::to_folded_case)
+    /// without building a `String`. Width, fill, alignment and precision
+    /// apply to the folded form.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let a = UniCase::new("Maße");
+    /// assert_eq!(format!("{} -> {}", a.display_original(), a.display_folded()), "Maße -> masse");
+    /// ```
+    #[inline]
+    pub fn display_folded(&self) -> DisplayFolded<'_> {
+        DisplayFolded {
+            s: self.as_ref(),
+            ascii: self.folds_as_ascii(),
+        }
+    }
+}
+
+impl<'a, S: fmt::Display> fmt::Display for DisplayOriginal<'a, S> {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.0, fmt)
+    }
+}
+
+impl<'a, S: fmt::Debug> fmt::Debug for DisplayOriginal<'a, S> {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.0, fmt)
+    }
+}
+
+impl<'a> DisplayFolded<'a> {
+    fn chars(&self) -> FoldedChars<'a> {
+        FoldedChars::new(self.s, self.ascii)
+    }
+}
+
+impl<'a> fmt::Display for DisplayFolded<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let max = fmt.precision().unwrap_or(!0);
+        let width = match fmt.width() {
+            Some(width) => width,
+            None => {
+                for c in self.chars().take(max) {
+                    fmt.write_char(c)?;
+                }
+                return Ok(());
+            }
+        };
+
+        // counting needs a second pass over the fold, but no allocation
+        let len = self.chars().take(max).count();
+        let padding = width.saturating_sub(len);
+        let (before, after) = match fmt.align() {
+            Some(fmt::Alignment::Right) => (padding, 0),
+            Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+            _ => (0, padding),
+        };
+        let fill = fmt.fill();
+        for _ in 0..before {
+            fmt.write_char(fill)?;
+        }
+        for c in self.chars().take(max) {
+            fmt.write_char(c)?;
+        }
+        for _ in 0..after {
+            fmt.write_char(fill)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Debug for DisplayFolded<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_char('"')?;
+        for c in self.chars() {
+            for e in c.escape_debug() {
+                fmt.write_char(e)?;
+            }
+        }
+        fmt.write_char('"')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::UniCase;
+    use std::format;
+
+    #[test]
+    fn test_display_folded() {
+        for &s in &["FooBar", "Maße", "ΣΊΣΥΦΟΣ", ""] {
+            let a = UniCase::new(s);
+            assert_eq!(format!("{}", a.display_folded()), a.to_folded_case());
+            assert_eq!(format!("{}", a.display_original()), s);
+        }
+        assert_eq!(format!("{}", UniCase::ascii("ÄB").display_folded()), "äb");
+        assert_eq!(
+            format!("{:?}", UniCase::new("A\"ß").display_folded()),
+            "\"a\\\"ss\""
+        );
+    }
+
+    #[test]
+    fn test_display_folded_padding() {
+        let a = UniCase::new("Maße");
+        assert_eq!(format!("{:7}|", a.display_folded()), "masse  |");
+        assert_eq!(format!("{:>7}|", a.display_folded()), "  masse|");
+        assert_eq!(format!("{:*^8}|", a.display_folded()), "*masse**|");
+        assert_eq!(format!("{:.3}|", a.display_folded()), "mas|");
+        assert_eq!(format!("{:5.3}|", a.display_folded()), "mas  |");
+        assert_eq!(format!("{:3}|", a.display_folded()), "masse|");
+        assert_eq!(format!("{:7}|", a.display_original()), "Maße   |");
+    }
+}
@@ -0,0 +1,133 @@
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+use super::hash::hash_bytes;
+use super::UniCase;
+
+/// Case insensitive wrapper of DNS names, which also ignores a trailing dot.
+///
+/// Names are compared as DNS does (RFC 4343): only ASCII letters fold, and
+/// every other byte, including non-ASCII text, only equals itself. On top of
+/// that, a single trailing `.` is dropped before comparing, so the fully
+/// qualified `"example.com."` equals `"example.com"`. Only one dot is
+/// dropped, and only at the end: `"example.com.."` and `".example.com"` are
+/// distinct names, and the root `"."` equals `""`.
+///
+/// Use it for host names gathered from URLs, certificates or zone files,
+/// where the absolute and relative spellings of one name meet. It doesn't
+/// check that the name is valid, nor convert IDNs: compare their ASCII
+/// (`xn--`) forms. A `DnsName` only compares with other `DnsName`s.
+///
+/// # Example
+///
+/// ```rust
+/// use unicase::DnsName;
+///
+/// let a = DnsName::new("Example.COM.");
+/// let b = DnsName::new("example.com");
+///
+/// assert_eq!(a, b);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DnsName<S>(S);
+
+impl<S> DnsName<S> {
+    /// Creates a new `DnsName`.
+    #[inline]
+    pub const fn new(s: S) -> DnsName<S> {
+        DnsName(s)
+    }
+
+    /// Unwraps the inner value.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+impl<S> UniCase<S> {
+    /// Creates a case insensitive DNS name, which only folds ASCII and also
+    /// ignores a trailing dot.
+    ///
+    /// Shorthand for [`DnsName::new`]. Unlike `UniCase`, only ASCII folds
+    /// and the trailing dot is ignored, so the result is not a `UniCase`.
+    #[inline]
+    pub const fn dns_name(s: S) -> DnsName<S> {
+        DnsName(s)
+    }
+}
+
+impl<S: AsRef<str>> DnsName<S> {
+    /// The bytes that are compared: the name without its trailing dot.
+    fn name(&self) -> &[u8] {
+        let s = self.0.as_ref().as_bytes();
+        match s.split_last() {
+            Some((b'.', rest)) => rest,
+            _ => s,
+        }
+    }
+}
+
+impl<S: AsRef<str>> AsRef<str> for DnsName<S> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+impl<S: fmt::Display> fmt::Display for DnsName<S> {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, fmt)
+    }
+}
+
+impl<S1: AsRef<str>, S2: AsRef<str>> PartialEq<DnsName<S2>> for DnsName<S1> {
+    #[inline]
+    fn eq(&self, other: &DnsName<S2>) -> bool {
+        self.name().eq_ignore_ascii_case(other.name())
+    }
+}
+
+impl<S: AsRef<str>> Eq for DnsName<S> {}
+
+impl<S: AsRef<str>> Hash for DnsName<S> {
+    #[inline]
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        hash_bytes(self.name().iter().map(u8::to_ascii_lowercase), hasher)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DnsName;
+    use crate::hash::hash_of;
+    use crate::UniCase;
+
+    #[test]
+    fn test_dns_name_trailing_dot() {
+        let a = UniCase::dns_name("Example.COM.");
+        let b = DnsName::new("example.com");
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+        assert_eq!(a, DnsName::new("EXAMPLE.com."));
+        assert_eq!(DnsName::new("."), DnsName::new(""));
+
+        // only a single trailing dot
+        for &s in &[
+            "example.com..",
+            ".example.com",
+            "example..com",
+            "example.co",
+        ] {
+            assert!(DnsName::new(s) != b, "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn test_dns_name_folds_only_ascii() {
+        assert!(DnsName::new("bücher.de") != DnsName::new("BÜCHER.de"));
+        assert_eq!(DnsName::new("bücher.DE."), DnsName::new("bücher.de"));
+        assert!(DnsName::new("\u{212A}.example") != DnsName::new("k.example"));
+    }
+}
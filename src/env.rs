@@ -0,0 +1,59 @@
+//! Case-insensitive lookups into the process environment.
+//!
+//! Environment variable names are case-insensitive on Windows, but not on
+//! other platforms. These helpers look names up ignoring ASCII case
+//! everywhere, which is useful for tools that want the same behavior on all
+//! of them.
+//!
+//! Only available with the `std` feature.
+
+use std::string::String;
+
+use super::eq_ascii;
+
+/// Returns the value of the environment variable `name`, ignoring ASCII case.
+///
+/// A variable named exactly `name` is preferred. Otherwise the environment
+/// is scanned, which is O(n) in its size, and the first variable whose name
+/// matches is returned. On platforms where names are case-sensitive, several
+/// may match, and which one comes first is unspecified.
+///
+/// Variables whose name or value is not valid Unicode are skipped.
+///
+/// # Example
+///
+/// ```rust
+/// std::env::set_var("UNICASE_DOC_EXAMPLE", "1");
+/// assert_eq!(unicase::env::var_ci("unicase_doc_example").as_deref(), Some("1"));
+/// ```
+pub fn var_ci(name: &str) -> Option<String> {
+    if let Ok(value) = std::env::var(name) {
+        return Some(value);
+    }
+    for (key, value) in std::env::vars_os() {
+        let matches = match key.to_str() {
+            Some(key) => eq_ascii(key, name),
+            None => false,
+        };
+        if matches {
+            if let Ok(value) = value.into_string() {
+                return Some(value);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::var_ci;
+
+    #[test]
+    fn test_var_ci() {
+        std::env::set_var("UNICASE_TEST_VAR_CI", "Some Value");
+        assert_eq!(var_ci("unicase_test_var_ci").as_deref(), Some("Some Value"));
+        assert_eq!(var_ci("Unicase_Test_Var_Ci").as_deref(), Some("Some Value"));
+        assert_eq!(var_ci("UNICASE_TEST_VAR_CI").as_deref(), Some("Some Value"));
+        assert_eq!(var_ci("UNICASE_TEST_VAR_C"), None);
+    }
+}
@@ -0,0 +1,216 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem;
+use std::collections::HashMap;
+
+use super::{UniCase, UniCaseNoOpt};
+
+/// A map with string keys that are grouped case-insensitively, but kept
+/// apart by their exact casing.
+///
+/// This is for case-preserving namespaces that still let variants collide,
+/// like a case-insensitive filesystem that was copied from a case-sensitive
+/// one: `"README"` and `"readme"` are two entries, and both are found by a
+/// case-insensitive lookup, while an exact lookup tells them apart.
+///
+/// Keys are grouped by `UniCase` equality, and within a group, variants are
+/// kept in the order they were first inserted.
+///
+/// Only available with the `std` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use unicase::CaseFoldMap;
+///
+/// let mut files = CaseFoldMap::new();
+/// files.insert("README", 1);
+/// files.insert("readme", 2);
+///
+/// assert_eq!(files.get_ci("ReadMe").collect::<Vec<_>>(), [&1, &2]);
+/// assert_eq!(files.get_exact("readme"), Some(&2));
+/// assert_eq!(files.get_exact("ReadMe"), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CaseFoldMap<V> {
+    groups: HashMap<UniCase<String>, Vec<(String, V)>>,
+    len: usize,
+}
+
+impl<V> CaseFoldMap<V> {
+    /// Creates an empty map.
+    #[inline]
+    pub fn new() -> CaseFoldMap<V> {
+        CaseFoldMap {
+            groups: HashMap::new(),
+            len: 0,
+        }
+    }
+
+    /// Inserts a value, returning the old one if the key was already present
+    /// with exactly the same casing.
+    ///
+    /// A key that only matches existing ones case-insensitively is added as
+    /// another variant next to them.
+    pub fn insert(&mut self, key: impl Into<String>, value: V) -> Option<V> {
+        let key = key.into();
+        if let Some(group) = self.groups.get_mut(UniCaseNoOpt::from_ref(&key)) {
+            if let Some(entry) = group.iter_mut().find(|(k, _)| *k == key) {
+                return Some(mem::replace(&mut entry.1, value));
+            }
+            group.push((key, value));
+        } else {
+            self.groups
+                .insert(UniCase::new(key.clone()), vec![(key, value)]);
+        }
+        self.len += 1;
+        None
+    }
+
+    /// Returns the values of every variant of a key, ignoring case, in the
+    /// order they were first inserted.
+    pub fn get_ci(&self, key: &str) -> impl Iterator<Item = &V> {
+        self.variants(key).map(|(_, v)| v)
+    }
+
+    /// Returns every variant of a key, ignoring case, with its exact casing,
+    /// in the order they were first inserted.
+    pub fn variants(&self, key: &str) -> impl Iterator<Item = (&str, &V)> {
+        self.groups
+            .get(UniCaseNoOpt::from_ref(key))
+            .into_iter()
+            .flatten()
+            .map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Returns the value for a key with exactly this casing.
+    pub fn get_exact(&self, key: &str) -> Option<&V> {
+        self.groups
+            .get(UniCaseNoOpt::from_ref(key))?
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    /// Returns a mutable reference to the value for a key with exactly this
+    /// casing.
+    pub fn get_exact_mut(&mut self, key: &str) -> Option<&mut V> {
+        self.groups
+            .get_mut(UniCaseNoOpt::from_ref(key))?
+            .iter_mut()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    /// Returns `true` if any variant of a key is present, ignoring case.
+    #[inline]
+    pub fn contains_ci(&self, key: &str) -> bool {
+        self.groups.contains_key(UniCaseNoOpt::from_ref(key))
+    }
+
+    /// Removes a key with exactly this casing, and returns its value.
+    ///
+    /// Other variants of the key stay in the map, in the same order.
+    pub fn remove_exact(&mut self, key: &str) -> Option<V> {
+        let group = self.groups.get_mut(UniCaseNoOpt::from_ref(key))?;
+        let i = group.iter().position(|(k, _)| k == key)?;
+        let (_, value) = group.remove(i);
+        if group.is_empty() {
+            self.groups.remove(UniCaseNoOpt::from_ref(key));
+        }
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Returns the number of entries, counting each variant separately.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the map has no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Iterates over every entry, with its exact key.
+    ///
+    /// Groups come in an arbitrary order, like a `HashMap`'s, but the
+    /// variants within one group are adjacent and in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &V)> {
+        self.groups.values().flatten().map(|(k, v)| (k.as_str(), v))
+    }
+}
+
+impl<V> Default for CaseFoldMap<V> {
+    #[inline]
+    fn default() -> CaseFoldMap<V> {
+        CaseFoldMap::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CaseFoldMap;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_exact_variants_coexist() {
+        let mut map = CaseFoldMap::new();
+        assert_eq!(map.insert("README", 1), None);
+        assert_eq!(map.insert("readme", 2), None);
+        assert_eq!(map.insert("Cargo.toml", 3), None);
+        assert_eq!(map.len(), 3);
+
+        assert_eq!(map.get_exact("README"), Some(&1));
+        assert_eq!(map.get_exact("readme"), Some(&2));
+        assert_eq!(map.get_exact("Readme"), None);
+        assert_eq!(map.get_ci("Readme").collect::<Vec<_>>(), [&1, &2]);
+        assert_eq!(
+            map.variants("rEADME").collect::<Vec<_>>(),
+            [("README", &1), ("readme", &2)]
+        );
+        assert!(map.contains_ci("CARGO.TOML"));
+        assert_eq!(map.get_ci("LICENSE").count(), 0);
+
+        // only the exact casing is replaced
+        assert_eq!(map.insert("readme", 20), Some(2));
+        *map.get_exact_mut("README").unwrap() += 10;
+        assert_eq!(map.get_ci("readme").collect::<Vec<_>>(), [&11, &20]);
+        assert_eq!(map.len(), 3);
+    }
+
+    #[test]
+    fn test_unicode_groups() {
+        let mut map = CaseFoldMap::new();
+        map.insert("Maße", 1);
+        map.insert("MASSE", 2);
+        assert_eq!(map.get_ci("masse").collect::<Vec<_>>(), [&1, &2]);
+        assert_eq!(map.get_exact("MASSE"), Some(&2));
+    }
+
+    #[test]
+    fn test_remove_exact() {
+        let mut map = CaseFoldMap::new();
+        map.insert("README", 1);
+        map.insert("readme", 2);
+        map.insert("ReadMe", 3);
+
+        assert_eq!(map.remove_exact("Readme"), None);
+        assert_eq!(map.remove_exact("README"), Some(1));
+        assert_eq!(map.get_ci("readme").collect::<Vec<_>>(), [&2, &3]);
+
+        // the group outlives the variant it was created with
+        map.insert("README", 4);
+        assert_eq!(map.get_ci("readme").collect::<Vec<_>>(), [&2, &3, &4]);
+
+        map.remove_exact("readme");
+        map.remove_exact("ReadMe");
+        map.remove_exact("README");
+        assert!(!map.contains_ci("readme"));
+        assert!(map.is_empty());
+        assert_eq!(map.iter().count(), 0);
+    }
+}
@@ -0,0 +1,73 @@
+use alloc::string::String;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{FoldedChars, UniCase};
+
+impl<S: AsRef<str>> UniCase<S> {
+    /// Splits the original string into extended grapheme clusters, and
+    /// yields each one case-folded.
+    ///
+    /// Clusters are found in the string as written, so a base letter stays
+    /// together with its combining marks, and an emoji with its modifiers
+    /// and joiners. Folding then works per code point within each cluster,
+    /// exactly like [`to_folded_case`](UniCase::to_folded_case), so joining
+    /// every item gives the same string. A cluster can fold to more than one
+    /// char, as `"ß"` folds to `"ss"`, and nothing is normalized: `"é"` and
+    /// `"e\u{301}"` stay different.
+    ///
+    /// Only available with the `grapheme` feature.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let s = UniCase::new("E\u{301}ß👍🏽");
+    /// let graphemes: Vec<String> = s.folded_graphemes().collect();
+    /// assert_eq!(graphemes, ["e\u{301}", "ss", "👍🏽"]);
+    /// ```
+    pub fn folded_graphemes(&self) -> impl Iterator<Item = String> + '_ {
+        let ascii = self.folds_as_ascii();
+        self.as_ref()
+            .graphemes(true)
+            .map(move |g| FoldedChars::new(g, ascii).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::UniCase;
+    use std::string::String;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_folded_graphemes() {
+        let s = UniCase::new("Cafe\u{301} A\u{308}\u{323}b");
+        assert_eq!(
+            s.folded_graphemes().collect::<Vec<_>>(),
+            ["c", "a", "f", "e\u{301}", " ", "a\u{308}\u{323}", "b"]
+        );
+
+        // emoji sequences stay whole, and a cluster can grow when folded
+        let s = UniCase::new("ﬁ👨‍👩‍👧🇩🇪SS");
+        assert_eq!(
+            s.folded_graphemes().collect::<Vec<_>>(),
+            ["fi", "👨‍👩‍👧", "🇩🇪", "s", "s"]
+        );
+
+        assert_eq!(UniCase::new("").folded_graphemes().count(), 0);
+    }
+
+    #[test]
+    fn test_folded_graphemes_join_to_folded_case() {
+        for &s in &["Maße", "ΣΊΣΥΦΟΣ", "e\u{301}E\u{301}", "Hello, World"] {
+            let s = UniCase::new(s);
+            let joined: String = s.folded_graphemes().collect();
+            assert_eq!(joined, s.to_folded_case(), "{:?}", s);
+        }
+        // and like it, non-ASCII text gets the full fold even in ASCII mode
+        let s = UniCase::ascii("ÄB");
+        assert_eq!(s.folded_graphemes().collect::<Vec<_>>(), ["ä", "b"]);
+    }
+}
@@ -0,0 +1,553 @@
+#[cfg(feature = "bstr")]
+use core::array;
+use core::hash::Hasher;
+#[cfg(feature = "bstr")]
+use core::iter::Take;
+
+use super::unicode::lookup;
+use super::{FoldedChars, UniCase};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Hash streams are handed to the hasher in chunks of this many bytes,
+/// gathered in a stack buffer.
+///
+/// Chunks are cut by byte count alone, so the calls a hasher sees depend only
+/// on the stream: values that compare equal make the same calls, and hash the
+/// same with any hasher, not only ones that stream their input like
+/// `DefaultHasher`. And one `write` is much cheaper than a `write_u8` per
+/// byte for most hashers: the `bench_fold_hash_*` benches below
+/// (`--features nightly`) put chunks at about 1.7x as fast for 8 bytes with
+/// `DefaultHasher`, and about 4x from 32 bytes on.
+///
+/// The size itself is picked by hand: chunks of 32 to 512 bytes made no
+/// difference beyond noise in the same benches. 128 bytes keeps typical keys
+/// in a single call, and the buffer small on the stack of every hash call.
+const CHUNK_LEN: usize = 128;
+
+/// Feeds the canonical hash stream for `s` to `hasher`: the UTF-8 bytes of
+/// its full Unicode case fold, then a `0xFF` terminator.
+///
+/// Every case-insensitive wrapper hashes through this, whatever its folding
+/// mode, so any two values that compare equal also hash the same.
+#[inline]
+pub(crate) fn fold_hash<H: Hasher>(s: &str, hasher: &mut H) {
+    let mut chunks = Chunks::new(hasher);
+    for c in s.chars() {
+        if c.is_ascii() {
+            chunks.push(c.to_ascii_lowercase() as u8);
+            continue;
+        }
+        for folded in lookup(c) {
+            chunks.push_char(folded);
+        }
+    }
+    chunks.finish();
+}
+
+/// Feeds an already folded string to `hasher`, producing the same stream as
+/// [`fold_hash`] would for its unfolded form.
+#[inline]
+pub(crate) fn hash_folded<H: Hasher>(folded: &str, hasher: &mut H) {
+    hash_bytes(folded.bytes(), hasher)
+}
+
+/// Feeds `bytes` to `hasher`, then the terminator.
+///
+/// This is the hash stream for the wrappers with a relation of their own,
+/// such as [`ConfigKey`](crate::ConfigKey), which compare a transformed
+/// stream rather than the plain fold: each passes the stream it compares.
+#[inline]
+pub(crate) fn hash_bytes<H: Hasher>(bytes: impl IntoIterator<Item = u8>, hasher: &mut H) {
+    let mut chunks = Chunks::new(hasher);
+    for b in bytes {
+        chunks.push(b);
+    }
+    chunks.finish();
+}
+
+/// Like [`hash_bytes`], for a stream of chars, which are written as UTF-8.
+#[inline]
+pub(crate) fn hash_chars<H: Hasher>(chars: impl IntoIterator<Item = char>, hasher: &mut H) {
+    let mut chunks = Chunks::new(hasher);
+    for c in chars {
+        chunks.push_char(c);
+    }
+    chunks.finish();
+}
+
+/// The UTF-8 bytes of `c`.
+#[cfg(feature = "bstr")]
+#[clippy::msrv = "1.65"]
+#[inline]
+pub(crate) fn utf8_bytes(c: char) -> Take<array::IntoIter<u8, 4>> {
+    let mut buf = [0; 4];
+    let len = char_to_utf8(c, &mut buf);
+    IntoIterator::into_iter(buf).take(len)
+}
+
+/// Cuts a hash stream into [`CHUNK_LEN`] byte `write`s.
+struct Chunks<'a, H> {
+    hasher: &'a mut H,
+    buf: [u8; CHUNK_LEN],
+    len: usize,
+    /// Whether a full chunk has been written already.
+    #[cfg(__unicase__write_str)]
+    written: bool,
+}
+
+impl<'a, H: Hasher> Chunks<'a, H> {
+    #[inline]
+    fn new(hasher: &'a mut H) -> Self {
+        Chunks {
+            hasher,
+            buf: [0; CHUNK_LEN],
+            len: 0,
+            #[cfg(__unicase__write_str)]
+            written: false,
+        }
+    }
+
+    #[inline]
+    fn push(&mut self, b: u8) {
+        // a full chunk is only written once more bytes follow, so the last
+        // one is never empty unless the whole stream is
+        if self.len == CHUNK_LEN {
+            self.hasher.write(&self.buf);
+            self.len = 0;
+            #[cfg(__unicase__write_str)]
+            {
+                self.written = true;
+            }
+        }
+        self.buf[self.len] = b;
+        self.len += 1;
+    }
+
+    #[inline]
+    fn push_char(&mut self, c: char) {
+        let mut utf8 = [0; 4];
+        let len = char_to_utf8(c, &mut utf8);
+        for &b in &utf8[..len] {
+            self.push(b);
+        }
+    }
+
+    /// Writes the last chunk, then the terminator.
+    ///
+    /// Where `Hasher::write_str` is available, a stream that fits in one chunk
+    /// and is UTF-8 is just that call. Its default is exactly the `write` and
+    /// `write_u8` below, so hashers that don't override it see no difference,
+    /// while string-aware ones get a single call.
+    #[inline]
+    fn finish(self) {
+        let rest = &self.buf[..self.len];
+        #[cfg(__unicase__write_str)]
+        {
+            if !self.written {
+                if let Ok(s) = core::str::from_utf8(rest) {
+                    self.hasher.write_str(s);
+                    return;
+                }
+            }
+        }
+        self.hasher.write(rest);
+        // prefix-freedom
+        self.hasher.write_u8(0xFF);
+    }
+}
+
+/// Checks that two strings that compared equal have the same canonical
+/// hash stream, as `Hash` requires.
+///
+/// Every `==` between `UniCase`s that returns `true` runs this in debug
+/// builds, so a comparison that drifts from the hash stream (such as ASCII
+/// folding where the hash uses Unicode folding) fails loudly in tests rather
+/// than as missed map lookups. It is compiled out of release builds.
+#[cfg(debug_assertions)]
+#[inline]
+pub(crate) fn debug_check_eq(a: &str, b: &str) {
+    debug_assert!(
+        FoldedChars::new(a, false).eq(FoldedChars::new(b, false)),
+        "{:?} and {:?} compare equal but fold differently",
+        a,
+        b
+    );
+}
+
+#[inline]
+fn char_to_utf8(c: char, dst: &mut [u8; 4]) -> usize {
+    const TAG_CONT: u8 = 0b1000_0000;
+    const TAG_TWO_B: u8 = 0b1100_0000;
+    const TAG_THREE_B: u8 = 0b1110_0000;
+    const TAG_FOUR_B: u8 = 0b1111_0000;
+
+    let code = c as u32;
+    if code <= 0x7F {
+        dst[0] = code as u8;
+        1
+    } else if code <= 0x7FF {
+        dst[0] = (code >> 6 & 0x1F) as u8 | TAG_TWO_B;
+        dst[1] = (code & 0x3F) as u8 | TAG_CONT;
+        2
+    } else if code <= 0xFFFF {
+        dst[0] = (code >> 12 & 0x0F) as u8 | TAG_THREE_B;
+        dst[1] = (code >> 6 & 0x3F) as u8 | TAG_CONT;
+        dst[2] = (code & 0x3F) as u8 | TAG_CONT;
+        3
+    } else {
+        dst[0] = (code >> 18 & 0x07) as u8 | TAG_FOUR_B;
+        dst[1] = (code >> 12 & 0x3F) as u8 | TAG_CONT;
+        dst[2] = (code >> 6 & 0x3F) as u8 | TAG_CONT;
+        dst[3] = (code & 0x3F) as u8 | TAG_CONT;
+        4
+    }
+}
+
+/// Computes [`UniCase::stable_hash`] in a `const` context.
+///
+/// This makes it possible to build `static` tables keyed on the hashes of
+/// known strings. Only ASCII case folding can be done at compile time, so
+/// `s` must be all ASCII: other input panics, which is a compile error when
+/// evaluated as a constant.
+///
+/// Requires Rust 1.57 or newer.
+///
+/// # Example
+///
+/// ```rust
+/// use unicase::{const_fold_hash, UniCase};
+///
+/// const CONTENT_TYPE: u64 = const_fold_hash("Content-Type");
+///
+/// assert_eq!(CONTENT_TYPE, UniCase::new("content-type").stable_hash());
+/// ```
+#[cfg(__unicase__const_fold_hash)]
+#[clippy::msrv = "1.57"]
+pub const fn const_fold_hash(s: &str) -> u64 {
+    let bytes = s.as_bytes();
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        assert!(b.is_ascii(), "const_fold_hash only supports ASCII strings");
+        hash ^= b.to_ascii_lowercase() as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+        i += 1;
+    }
+    hash
+}
+
+impl<S: AsRef<str>> UniCase<S> {
+    /// Returns a hash of the case-folded string that never changes between
+    /// runs, platforms, or versions of this crate.
+    ///
+    /// The algorithm is 64-bit FNV-1a over the UTF-8 bytes of the full
+    /// Unicode case fold, with no terminator. It can be reproduced anywhere,
+    /// which makes it suitable for tables built ahead of time and looked up
+    /// at runtime.
+    ///
+    /// The full fold is used whatever the folding mode, like the `Hash`
+    /// impl, since a value forced into ASCII mode still compares equal to a
+    /// Unicode one by Unicode folding.
+    ///
+    /// This is separate from the `Hash` impl, which feeds a `Hasher` and so
+    /// depends on whichever hasher (and seed) the collection uses.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// assert_eq!(UniCase::new("FooBar").stable_hash(), 0x8594_4171_f739_67e8);
+    /// assert_eq!(
+    ///     UniCase::new("Maße").stable_hash(),
+    ///     UniCase::new("MASSE").stable_hash(),
+    /// );
+    /// ```
+    pub fn stable_hash(&self) -> u64 {
+        let mut hash = FNV_OFFSET_BASIS;
+        let mut buf = [0; 4];
+        for c in FoldedChars::new(self.as_ref(), false) {
+            for &b in c.encode_utf8(&mut buf).as_bytes() {
+                hash ^= u64::from(b);
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        }
+        hash
+    }
+}
+
+/// Hashes `t` with `DefaultHasher`, for the tests of every module.
+#[cfg(test)]
+pub(crate) fn hash_of<T: core::hash::Hash + ?Sized>(t: &T) -> u64 {
+    let mut s = std::collections::hash_map::DefaultHasher::new();
+    t.hash(&mut s);
+    s.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hash_of;
+    use crate::{Ascii, LazyFolded, UniCase, UniCaseNoOpt};
+    use std::hash::{Hash, Hasher};
+
+    #[test]
+    fn test_hash_consistent_across_wrappers() {
+        for &(a, b) in &[("FooBar", "foobar"), ("Maße", "MASSE"), ("Ä", "ä")] {
+            let expected = hash_of(&UniCase::new(b));
+            assert_eq!(hash_of(&UniCase::new(a)), expected, "{:?}", a);
+            assert_eq!(hash_of(&UniCase::ascii(a)), expected, "{:?}", a);
+            assert_eq!(hash_of(&UniCase::unicode(a)), expected, "{:?}", a);
+            assert_eq!(hash_of(&Ascii::new(a)), expected, "{:?}", a);
+            assert_eq!(hash_of(UniCaseNoOpt::from_ref(a)), expected, "{:?}", a);
+            assert_eq!(hash_of(&LazyFolded::new(a)), expected, "{:?}", a);
+        }
+    }
+
+    #[test]
+    fn test_mixed_mode_equal_values_hash_equal() {
+        // a forced ASCII value compares with Unicode folding against a
+        // Unicode one, so it has to hash the same way
+        let a = UniCase::ascii("Ä");
+        let b = UniCase::unicode("ä");
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+    }
+
+    /// Records each call, so chunking can be told apart.
+    #[derive(Default)]
+    struct Calls(std::vec::Vec<std::string::String>);
+
+    impl Hasher for Calls {
+        fn write(&mut self, bytes: &[u8]) {
+            self.0.push(std::format!("{:?}", bytes));
+        }
+        fn write_u8(&mut self, b: u8) {
+            self.0.push(std::format!("{}", b));
+        }
+        #[cfg(__unicase__write_str)]
+        fn write_str(&mut self, s: &str) {
+            self.0.push(std::string::String::from(s));
+        }
+        fn finish(&self) -> u64 {
+            0
+        }
+    }
+
+    fn calls<T: Hash + ?Sized>(t: &T) -> std::vec::Vec<std::string::String> {
+        let mut c = Calls::default();
+        t.hash(&mut c);
+        c.0
+    }
+
+    #[test]
+    fn test_chunks_by_folded_len() {
+        use super::CHUNK_LEN;
+
+        // a whole fold in one call
+        let short = calls(&UniCase::new("Maße"));
+        assert_eq!(short.len(), if cfg!(__unicase__write_str) { 1 } else { 2 });
+        assert_eq!(short, calls(&LazyFolded::new("MASSE")));
+
+        // chunks go by the folded length, which equal values share, however
+        // long they are as written
+        let fits = "\u{212A}".repeat(CHUNK_LEN);
+        assert_eq!(fits.len(), CHUNK_LEN * 3);
+        assert_eq!(calls(&UniCase::new(&*fits)).len(), short.len());
+        let over = "ẞ".repeat(CHUNK_LEN / 2 + 1);
+        assert_eq!(calls(&UniCase::new(&*over)).len(), 3);
+        let long = "ẞ".repeat(CHUNK_LEN * 2);
+        assert_eq!(calls(&UniCase::new(&*long)).len(), 5);
+        for &(a, b) in &[(&*fits, "k"), (&*over, "SS"), (&*long, "ss")] {
+            let b = b.repeat(a.chars().count());
+            assert_eq!(calls(&UniCase::new(a)), calls(&UniCase::new(&*b)));
+            assert_eq!(calls(&UniCase::new(a)), calls(&LazyFolded::new(&*b)));
+            assert_eq!(calls(&UniCase::new(a)), calls(UniCaseNoOpt::from_ref(&b)));
+            assert_eq!(calls(&UniCase::new(a)), calls(&Ascii::new(&*b)));
+        }
+    }
+
+    #[test]
+    fn test_calls_depend_only_on_fold() {
+        use super::CHUNK_LEN;
+
+        // however the folds line up with chunk boundaries, every hasher sees
+        // the same calls for equal values, and the stream is unchanged
+        for n in 0..CHUNK_LEN + 8 {
+            for part in &["a", "Ä", "ß", "\u{212A}", "ΐ", "\u{10400}"] {
+                let s = part.repeat(n);
+                let folded = UniCase::new(&*s).to_folded_case();
+                let expected = calls(&UniCase::new(&*folded));
+                assert_eq!(calls(&UniCase::new(&*s)), expected, "{:?} x {}", part, n);
+                assert_eq!(calls(&LazyFolded::new(&*s)), expected, "{:?} x {}", part, n);
+
+                let mut bytes = std::vec::Vec::from(folded.as_bytes());
+                bytes.push(0xFF);
+                assert_eq!(stream(&UniCase::new(&*s)), bytes, "{:?} x {}", part, n);
+            }
+        }
+    }
+
+    #[cfg(feature = "nightly")]
+    macro_rules! bench_fold_hash {
+        ($($write_u8:ident, $chunked:ident: $len:expr;)*) => {
+            $(
+                #[bench]
+                fn $write_u8(b: &mut ::test::Bencher) {
+                    // the same stream a byte at a time, for comparison
+                    let s = "Ab".repeat($len / 2);
+                    b.iter(|| {
+                        let mut h = std::collections::hash_map::DefaultHasher::new();
+                        let mut buf = [0; 4];
+                        for c in ::test::black_box(&s).chars().flat_map(super::lookup) {
+                            for &b in c.encode_utf8(&mut buf).as_bytes() {
+                                h.write_u8(b);
+                            }
+                        }
+                        h.write_u8(0xFF);
+                        h.finish()
+                    });
+                }
+
+                #[bench]
+                fn $chunked(b: &mut ::test::Bencher) {
+                    let s = "Ab".repeat($len / 2);
+                    b.iter(|| {
+                        let mut h = std::collections::hash_map::DefaultHasher::new();
+                        super::fold_hash(::test::black_box(&s), &mut h);
+                        h.finish()
+                    });
+                }
+            )*
+        };
+    }
+
+    #[cfg(feature = "nightly")]
+    bench_fold_hash! {
+        bench_fold_hash_write_u8_8, bench_fold_hash_chunked_8: 8;
+        bench_fold_hash_write_u8_32, bench_fold_hash_chunked_32: 32;
+        bench_fold_hash_write_u8_128, bench_fold_hash_chunked_128: 128;
+        bench_fold_hash_write_u8_512, bench_fold_hash_chunked_512: 512;
+        bench_fold_hash_write_u8_2048, bench_fold_hash_chunked_2048: 2048;
+    }
+
+    /// Records every byte written, however it was written.
+    #[derive(Default)]
+    struct Stream(std::vec::Vec<u8>);
+
+    impl Hasher for Stream {
+        fn write(&mut self, bytes: &[u8]) {
+            self.0.extend_from_slice(bytes);
+        }
+        fn finish(&self) -> u64 {
+            0
+        }
+    }
+
+    fn stream<T: Hash + ?Sized>(t: &T) -> std::vec::Vec<u8> {
+        let mut s = Stream::default();
+        t.hash(&mut s);
+        s.0
+    }
+
+    #[test]
+    fn test_hash_stream_is_terminated() {
+        assert_eq!(stream(&UniCase::new("Maße")), b"masse\xFF");
+        assert_eq!(stream(&UniCase::new("Maße")), stream("masse"));
+        assert_eq!(stream(&Ascii::new("AB")), stream("ab"));
+
+        // the terminator keeps segment boundaries apart
+        let split = |a: &'static str, b: &'static str| [UniCase::new(a), UniCase::new(b)];
+        assert!(stream(&split("ab", "c")) != stream(&split("a", "bc")));
+        assert!(stream(&split("AB", "")) != stream(&split("A", "B")));
+        assert!(
+            stream(&(UniCase::new("ß"), UniCase::new("x")))
+                != stream(&(UniCase::new("s"), UniCase::new("sx")))
+        );
+        assert!(hash_of(&split("ab", "c")) != hash_of(&split("a", "bc")));
+
+        // while the same segments in any casing still agree
+        assert_eq!(stream(&split("AB", "C")), stream(&split("ab", "c")));
+        assert_eq!(stream(&split("Maße", "x")), stream(&split("MASSE", "X")));
+    }
+
+    #[test]
+    fn test_stable_hash_vectors() {
+        // plain FNV-1a of the folded bytes
+        assert_eq!(UniCase::new("").stable_hash(), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(UniCase::new("A").stable_hash(), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(UniCase::new("foobar").stable_hash(), 0x8594_4171_f739_67e8);
+        assert_eq!(UniCase::new("Maße").stable_hash(), 0x8614_bda4_db13_663c);
+        assert_eq!(UniCase::new("ΣΊΣΥΦΟΣ").stable_hash(), 0xf1c8_6de9_6728_0162);
+    }
+
+    #[cfg(__unicase__const_fold_hash)]
+    #[test]
+    fn test_const_fold_hash() {
+        use super::const_fold_hash;
+
+        const EMPTY: u64 = const_fold_hash("");
+        const FOOBAR: u64 = const_fold_hash("FooBar");
+        static KEYS: [u64; 2] = [const_fold_hash("Accept"), const_fold_hash("HOST")];
+
+        assert_eq!(EMPTY, UniCase::new("").stable_hash());
+        assert_eq!(FOOBAR, 0x8594_4171_f739_67e8);
+        assert_eq!(KEYS[0], UniCase::new("accept").stable_hash());
+        assert_eq!(KEYS[1], UniCase::new("host").stable_hash());
+    }
+
+    #[cfg(__unicase__const_fold_hash)]
+    #[test]
+    #[should_panic(expected = "only supports ASCII")]
+    fn test_const_fold_hash_rejects_unicode() {
+        super::const_fold_hash("Maße");
+    }
+
+    #[test]
+    fn test_stable_hash_ignores_mode() {
+        assert_eq!(
+            UniCase::ascii("FooBar").stable_hash(),
+            UniCase::unicode("fooBAR").stable_hash()
+        );
+        assert_eq!(
+            UniCase::new("MASSE").stable_hash(),
+            UniCase::new("maße").stable_hash()
+        );
+
+        // a forced ASCII value holding non-ASCII text is equal to its
+        // Unicode-folded counterpart, so it must not hash its ASCII fold
+        let a = UniCase::ascii("Ä");
+        let b = UniCase::unicode("ä");
+        assert_eq!(a, b);
+        assert_eq!(a.stable_hash(), b.stable_hash());
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_debug_check_eq() {
+        // pairs that have compared equal with inconsistent folding before:
+        // mixed modes, and one string a folded prefix of the other
+        let pairs = [
+            (UniCase::ascii("Ä"), UniCase::unicode("ä")),
+            (UniCase::ascii("K"), UniCase::unicode("\u{212A}")),
+            (UniCase::new("Maße"), UniCase::new("MASSE")),
+            (UniCase::ascii("ÄB"), UniCase::ascii("Äb")),
+        ];
+        for &(a, b) in &pairs {
+            assert_eq!(a, b);
+            assert_eq!(b, a);
+            super::debug_check_eq(&a, &b);
+        }
+        assert!(UniCase::new("ß") != UniCase::new("s"));
+        assert!(UniCase::new("s") != UniCase::new("ß"));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "compare equal but fold differently")]
+    fn test_debug_check_eq_catches_mismatch() {
+        super::debug_check_eq("Ä", "ä\u{301}");
+    }
+}
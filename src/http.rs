@@ -0,0 +1,73 @@
+use ::http::header::{HeaderName, InvalidHeaderName};
+
+use super::{Ascii, UniCase};
+
+/// Parses the string as a header name.
+///
+/// `HeaderName` lowercases on the way in, so this accepts any casing.
+impl<'a> From<Ascii<&'a str>> for Result<HeaderName, InvalidHeaderName> {
+    fn from(s: Ascii<&'a str>) -> Self {
+        HeaderName::from_bytes(s.into_inner().as_bytes())
+    }
+}
+
+/// Parses the string as a header name.
+///
+/// `HeaderName` lowercases on the way in, so this accepts any casing.
+impl<'a> From<UniCase<&'a str>> for Result<HeaderName, InvalidHeaderName> {
+    fn from(s: UniCase<&'a str>) -> Self {
+        HeaderName::from_bytes(s.into_inner().as_bytes())
+    }
+}
+
+// `Ascii<S> == HeaderName` is covered by the `AsRef<str>` impl already.
+
+impl<S: AsRef<str>> PartialEq<Ascii<S>> for HeaderName {
+    #[inline]
+    fn eq(&self, other: &Ascii<S>) -> bool {
+        other == self
+    }
+}
+
+impl<S: AsRef<str>> PartialEq<HeaderName> for UniCase<S> {
+    #[inline]
+    fn eq(&self, other: &HeaderName) -> bool {
+        self.eq_ci(other.as_str())
+    }
+}
+
+impl<S: AsRef<str>> PartialEq<UniCase<S>> for HeaderName {
+    #[inline]
+    fn eq(&self, other: &UniCase<S>) -> bool {
+        other == self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Ascii, UniCase};
+    use http::header::{HeaderName, InvalidHeaderName, CONTENT_TYPE};
+
+    #[test]
+    fn test_into_header_name() {
+        let name: Result<HeaderName, InvalidHeaderName> = Ascii::new("Content-Type").into();
+        assert_eq!(name.unwrap(), CONTENT_TYPE);
+
+        let name: Result<HeaderName, InvalidHeaderName> = UniCase::new("X-Custom").into();
+        assert_eq!(name.unwrap().as_str(), "x-custom");
+
+        let name: Result<HeaderName, InvalidHeaderName> = Ascii::new("bad header").into();
+        assert!(name.is_err());
+    }
+
+    #[test]
+    fn test_eq_header_name() {
+        assert_eq!(Ascii::new("Content-Type"), CONTENT_TYPE);
+        assert_eq!(CONTENT_TYPE, Ascii::new("CONTENT-TYPE"));
+        assert!(Ascii::new("Content-Length") != CONTENT_TYPE);
+
+        assert_eq!(UniCase::new("Content-Type"), CONTENT_TYPE);
+        assert_eq!(CONTENT_TYPE, UniCase::new("content-TYPE"));
+        assert!(UniCase::new("Maße") != CONTENT_TYPE);
+    }
+}
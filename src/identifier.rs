@@ -0,0 +1,184 @@
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+use super::hash::hash_chars;
+use super::unicode::lookup;
+use super::UniCase;
+
+/// Case insensitive wrapper of identifiers, which also ignores invisible
+/// characters.
+///
+/// On top of full Unicode case folding, this skips every
+/// `Default_Ignorable_Code_Point`, as UAX #31 suggests for comparing
+/// identifiers: zero-width spaces and joiners, soft hyphens, bidi controls,
+/// variation selectors, and the like. So `"a\u{200B}b"` equals `"AB"`. This
+/// is useful when names that render the same must not be told apart, for
+/// instance to stop look-alike user or file names.
+///
+/// It is meant for checking names where they are registered or looked up;
+/// the wrapped string keeps its invisible characters, so store whichever
+/// form should be shown. An `Identifier` only compares with other
+/// `Identifier`s.
+///
+/// Only available with the `identifier` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use unicase::Identifier;
+///
+/// let a = Identifier::new("admin");
+/// let b = Identifier::new("AD\u{200D}MIN");
+///
+/// assert_eq!(a, b);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Identifier<S>(S);
+
+impl<S> Identifier<S> {
+    /// Creates a new `Identifier`.
+    #[inline]
+    pub const fn new(s: S) -> Identifier<S> {
+        Identifier(s)
+    }
+
+    /// Unwraps the inner value.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+impl<S> UniCase<S> {
+    /// Creates a case insensitive identifier, which also ignores
+    /// `Default_Ignorable_Code_Point` characters.
+    ///
+    /// Shorthand for [`Identifier::new`]. `UniCase` itself treats an
+    /// invisible character like any other, so the result is not a `UniCase`.
+    ///
+    /// Only available with the `identifier` feature.
+    #[inline]
+    pub const fn unicode_identifier(s: S) -> Identifier<S> {
+        Identifier(s)
+    }
+}
+
+impl<S: AsRef<str>> Identifier<S> {
+    fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        // no char folds to an ignorable one, so skipping first is enough
+        self.0
+            .as_ref()
+            .chars()
+            .filter(|&c| !is_default_ignorable(c))
+            .flat_map(lookup)
+    }
+}
+
+impl<S: AsRef<str>> AsRef<str> for Identifier<S> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+impl<S: fmt::Display> fmt::Display for Identifier<S> {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, fmt)
+    }
+}
+
+impl<S1: AsRef<str>, S2: AsRef<str>> PartialEq<Identifier<S2>> for Identifier<S1> {
+    #[inline]
+    fn eq(&self, other: &Identifier<S2>) -> bool {
+        self.chars().eq(other.chars())
+    }
+}
+
+impl<S: AsRef<str>> Eq for Identifier<S> {}
+
+impl<S: AsRef<str>> Hash for Identifier<S> {
+    #[inline]
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        hash_chars(self.chars(), hasher)
+    }
+}
+
+/// The `Default_Ignorable_Code_Point` ranges, from `DerivedCoreProperties.txt`.
+const DEFAULT_IGNORABLE: &[(char, char)] = &[
+    ('\u{AD}', '\u{AD}'),
+    ('\u{34F}', '\u{34F}'),
+    ('\u{61C}', '\u{61C}'),
+    ('\u{115F}', '\u{1160}'),
+    ('\u{17B4}', '\u{17B5}'),
+    ('\u{180B}', '\u{180F}'),
+    ('\u{200B}', '\u{200F}'),
+    ('\u{202A}', '\u{202E}'),
+    ('\u{2060}', '\u{206F}'),
+    ('\u{3164}', '\u{3164}'),
+    ('\u{FE00}', '\u{FE0F}'),
+    ('\u{FEFF}', '\u{FEFF}'),
+    ('\u{FFA0}', '\u{FFA0}'),
+    ('\u{FFF0}', '\u{FFF8}'),
+    ('\u{1BCA0}', '\u{1BCA3}'),
+    ('\u{1D173}', '\u{1D17A}'),
+    ('\u{E0000}', '\u{E0FFF}'),
+];
+
+fn is_default_ignorable(c: char) -> bool {
+    if c < '\u{AD}' {
+        return false;
+    }
+    DEFAULT_IGNORABLE
+        .binary_search_by(|&(lo, hi)| {
+            if hi < c {
+                core::cmp::Ordering::Less
+            } else if lo > c {
+                core::cmp::Ordering::Greater
+            } else {
+                core::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_default_ignorable, Identifier};
+    use crate::hash::hash_of;
+    use crate::UniCase;
+
+    #[test]
+    fn test_identifier_skips_ignorables() {
+        let a = UniCase::unicode_identifier("a\u{200B}b");
+        let b = Identifier::new("AB");
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+
+        for &s in &[
+            "ad\u{200D}min",
+            "\u{FEFF}admin",
+            "ad\u{AD}min\u{E0001}",
+            "\u{202E}ADMIN\u{202C}",
+            "admin\u{FE0F}",
+        ] {
+            let x = Identifier::new(s);
+            assert_eq!(x, Identifier::new("admin"), "{:?}", s);
+            assert_eq!(hash_of(&x), hash_of(&Identifier::new("admin")), "{:?}", s);
+        }
+
+        assert_eq!(Identifier::new("Maße"), Identifier::new("MAS\u{200C}SE"));
+        assert!(Identifier::new("a b") != Identifier::new("ab"));
+        assert!(Identifier::new("admin") != Identifier::new("admins"));
+    }
+
+    #[test]
+    fn test_is_default_ignorable() {
+        for &c in &['\u{AD}', '\u{200B}', '\u{2064}', '\u{FE0F}', '\u{E0FFF}'] {
+            assert!(is_default_ignorable(c), "{:?}", c);
+        }
+        for &c in &['a', ' ', '\u{AC}', '\u{200A}', '\u{2070}', '\u{E1000}'] {
+            assert!(!is_default_ignorable(c), "{:?}", c);
+        }
+    }
+}
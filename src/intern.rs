@@ -0,0 +1,127 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::collections::HashMap;
+
+use super::{UniCase, UniCaseNoOpt};
+
+/// An id handed out by a [`CaseInsensitiveInterner`].
+///
+/// Strings that are case-insensitively equal get the same `Symbol`, so
+/// symbols can be compared and hashed as plain integers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+/// Interns strings, giving case-insensitively equal ones the same
+/// [`Symbol`].
+///
+/// The first casing of a string that is interned is the one kept, and
+/// returned by [`resolve`](CaseInsensitiveInterner::resolve). Lookups use
+/// the same folding as `UniCase::new`, and only interning a new string
+/// allocates.
+///
+/// Only available with the `intern` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use unicase::CaseInsensitiveInterner;
+///
+/// let mut interner = CaseInsensitiveInterner::new();
+/// let a = interner.intern("Content-Type");
+/// let b = interner.intern("CONTENT-TYPE");
+///
+/// assert_eq!(a, b);
+/// assert_eq!(interner.resolve(b), "Content-Type");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CaseInsensitiveInterner {
+    ids: HashMap<UniCase<String>, Symbol>,
+    strings: Vec<String>,
+}
+
+impl CaseInsensitiveInterner {
+    /// Creates an empty interner.
+    pub fn new() -> CaseInsensitiveInterner {
+        CaseInsensitiveInterner::default()
+    }
+
+    /// Returns the symbol for `s`, interning it if no case-insensitively
+    /// equal string has been seen yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than `u32::MAX` distinct strings are interned.
+    pub fn intern(&mut self, s: &str) -> Symbol {
+        if let Some(sym) = self.get(s) {
+            return sym;
+        }
+        let sym = Symbol(self.strings.len() as u32);
+        assert!(
+            sym.0 as usize == self.strings.len(),
+            "interner ran out of symbols"
+        );
+        self.strings.push(s.to_owned());
+        self.ids.insert(UniCase::new(s.to_owned()), sym);
+        sym
+    }
+
+    /// Returns the symbol for `s`, if a case-insensitively equal string has
+    /// been interned.
+    pub fn get(&self, s: &str) -> Option<Symbol> {
+        self.ids.get(UniCaseNoOpt::from_ref(s)).cloned()
+    }
+
+    /// Returns the string a symbol was first interned with.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sym` was not handed out by this interner.
+    pub fn resolve(&self, sym: Symbol) -> &str {
+        &self.strings[sym.0 as usize]
+    }
+
+    /// Returns the number of distinct strings interned.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns `true` if nothing has been interned.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CaseInsensitiveInterner;
+
+    #[test]
+    fn test_intern() {
+        let mut interner = CaseInsensitiveInterner::new();
+        assert!(interner.is_empty());
+
+        let foo = interner.intern("Foo");
+        assert_eq!(interner.intern("FOO"), foo);
+        assert_eq!(interner.intern("foo"), foo);
+        assert_eq!(interner.resolve(foo), "Foo");
+
+        let bar = interner.intern("bar");
+        assert!(bar != foo);
+        assert_eq!(interner.resolve(bar), "bar");
+        assert_eq!(interner.len(), 2);
+
+        assert_eq!(interner.get("BAR"), Some(bar));
+        assert_eq!(interner.get("baz"), None);
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn test_intern_unicode() {
+        let mut interner = CaseInsensitiveInterner::new();
+        let a = interner.intern("Straße");
+        assert_eq!(interner.intern("STRASSE"), a);
+        assert_eq!(interner.intern("strasse"), a);
+        assert_eq!(interner.resolve(a), "Straße");
+    }
+}
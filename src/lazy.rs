@@ -0,0 +1,263 @@
+use alloc::string::String;
+use core::cell::{Ref, RefCell};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
+use super::hash::hash_folded;
+use super::unicode::Unicode;
+
+/// A case-insensitive string that remembers its folded form.
+///
+/// The first comparison or hash folds the string and caches the result, so
+/// values that are compared or hashed many times only pay for case folding
+/// once. The cache is invisible otherwise: equality and hashing behave
+/// exactly as they would for `UniCase`.
+///
+/// The cache uses a `RefCell`, so this type is not `Sync`. Use
+/// [`SyncLazyFolded`] to share values between threads.
+///
+/// # Example
+///
+/// ```rust
+/// use unicase::LazyFolded;
+///
+/// let a = LazyFolded::new("Maße");
+/// let b = LazyFolded::new("MASSE");
+///
+/// assert_eq!(a, b);
+/// assert_eq!(&*a.folded(), "masse");
+/// ```
+pub struct LazyFolded<S> {
+    inner: S,
+    folded: RefCell<Option<String>>,
+}
+
+impl<S: AsRef<str>> LazyFolded<S> {
+    /// Returns the case-folded form of the string, computing it on first use.
+    pub fn folded(&self) -> Ref<'_, str> {
+        if self.folded.borrow().is_none() {
+            *self.folded.borrow_mut() = Some(Unicode(self.inner.as_ref()).to_folded_case());
+        }
+        Ref::map(self.folded.borrow(), |folded| match *folded {
+            Some(ref s) => s.as_str(),
+            None => unreachable!("folded form was just computed"),
+        })
+    }
+}
+
+impl<S> LazyFolded<S> {
+    /// Creates a new `LazyFolded`. No folding happens until it is needed.
+    pub const fn new(s: S) -> LazyFolded<S> {
+        LazyFolded {
+            inner: s,
+            folded: RefCell::new(None),
+        }
+    }
+
+    /// Unwraps the inner value, dropping any cached folded form.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Clone> Clone for LazyFolded<S> {
+    fn clone(&self) -> Self {
+        LazyFolded {
+            inner: self.inner.clone(),
+            folded: self.folded.clone(),
+        }
+    }
+}
+
+/// A thread-safe version of [`LazyFolded`].
+///
+/// The folded form is cached in a `OnceLock`, so values can be shared (for
+/// instance in an `Arc`) and used from several threads at once.
+///
+/// Only available with the `std` feature.
+#[cfg(feature = "std")]
+#[clippy::msrv = "1.70"]
+pub struct SyncLazyFolded<S> {
+    inner: S,
+    folded: OnceLock<String>,
+}
+
+#[cfg(feature = "std")]
+#[clippy::msrv = "1.70"]
+impl<S: AsRef<str>> SyncLazyFolded<S> {
+    /// Returns the case-folded form of the string, computing it on first use.
+    pub fn folded(&self) -> &str {
+        self.folded
+            .get_or_init(|| Unicode(self.inner.as_ref()).to_folded_case())
+    }
+}
+
+#[cfg(feature = "std")]
+#[clippy::msrv = "1.70"]
+impl<S> SyncLazyFolded<S> {
+    /// Creates a new `SyncLazyFolded`. No folding happens until it is needed.
+    pub const fn new(s: S) -> SyncLazyFolded<S> {
+        SyncLazyFolded {
+            inner: s,
+            folded: OnceLock::new(),
+        }
+    }
+
+    /// Unwraps the inner value, dropping any cached folded form.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: Clone> Clone for SyncLazyFolded<S> {
+    fn clone(&self) -> Self {
+        SyncLazyFolded {
+            inner: self.inner.clone(),
+            folded: self.folded.clone(),
+        }
+    }
+}
+
+macro_rules! lazy_impls {
+    ($name:ident) => {
+        impl<S: AsRef<str>> AsRef<str> for $name<S> {
+            #[inline]
+            fn as_ref(&self) -> &str {
+                self.inner.as_ref()
+            }
+        }
+
+        impl<S: fmt::Debug> fmt::Debug for $name<S> {
+            #[inline]
+            fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Debug::fmt(&self.inner, fmt)
+            }
+        }
+
+        impl<S: fmt::Display> fmt::Display for $name<S> {
+            #[inline]
+            fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(&self.inner, fmt)
+            }
+        }
+
+        impl<S: Default> Default for $name<S> {
+            fn default() -> Self {
+                $name::new(S::default())
+            }
+        }
+
+        impl<S> From<S> for $name<S> {
+            fn from(s: S) -> Self {
+                $name::new(s)
+            }
+        }
+
+        impl<S1: AsRef<str>, S2: AsRef<str>> PartialEq<$name<S2>> for $name<S1> {
+            #[inline]
+            fn eq(&self, other: &$name<S2>) -> bool {
+                *self.folded() == *other.folded()
+            }
+        }
+
+        impl<S: AsRef<str>> Eq for $name<S> {}
+
+        impl<S: AsRef<str>> Hash for $name<S> {
+            #[inline]
+            fn hash<H: Hasher>(&self, hasher: &mut H) {
+                // the stream `hash::fold_hash` produces, without re-folding
+                hash_folded(&self.folded(), hasher)
+            }
+        }
+    };
+}
+
+lazy_impls!(LazyFolded);
+#[cfg(feature = "std")]
+lazy_impls!(SyncLazyFolded);
+
+#[cfg(test)]
+mod tests {
+    use super::LazyFolded;
+    use crate::hash::hash_of;
+    use crate::UniCase;
+    use std::string::String;
+
+    #[test]
+    fn test_lazy_folded_eq() {
+        let a = LazyFolded::new("Maße");
+        let b = LazyFolded::new(String::from("MASSE"));
+        let c = LazyFolded::new("mase");
+
+        assert_eq!(a, b);
+        assert_eq!(b, a);
+        assert!(a != c);
+        assert_eq!(hash_of(&a), hash_of(&b));
+    }
+
+    #[test]
+    fn test_lazy_folded_caches() {
+        let a = LazyFolded::new("FooBar");
+        assert!(a.folded.borrow().is_none());
+        assert_eq!(&*a.folded(), "foobar");
+        assert!(a.folded.borrow().is_some());
+
+        // cloning keeps the cache, and it doesn't change the answer
+        let b = a.clone();
+        assert_eq!(hash_of(&a), hash_of(&b));
+        assert_eq!(b.into_inner(), "FooBar");
+    }
+
+    #[test]
+    fn test_lazy_folded_hash_matches_unicase() {
+        assert_eq!(
+            hash_of(&LazyFolded::new("Maße")),
+            hash_of(&UniCase::new("MASSE"))
+        );
+        assert_eq!(
+            hash_of(&LazyFolded::new("FOOBAR")),
+            hash_of(&UniCase::new("foobar"))
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_sync_lazy_folded() {
+        use super::SyncLazyFolded;
+        use std::sync::Arc;
+        use std::thread;
+
+        let a = Arc::new(SyncLazyFolded::new(String::from("Maße")));
+        let b = a.clone();
+        let folded = thread::spawn(move || String::from(b.folded()))
+            .join()
+            .unwrap();
+
+        assert_eq!(folded, "masse");
+        assert_eq!(*a, SyncLazyFolded::new("MASSE"));
+        assert_eq!(hash_of(&*a), hash_of(&UniCase::new("masse")));
+    }
+
+    #[cfg(feature = "nightly")]
+    static LONG: &str =
+        "Ünïcödé Fööbär Bäz Ünïcödé Fööbär Bäz Ünïcödé Fööbär Bäz Ünïcödé Fööbär Bäz";
+
+    #[cfg(feature = "nightly")]
+    #[bench]
+    fn bench_lazy_folded_repeated_hash(b: &mut ::test::Bencher) {
+        let x = LazyFolded::new(LONG);
+        b.iter(|| hash_of(&x));
+    }
+
+    #[cfg(feature = "nightly")]
+    #[bench]
+    fn bench_unicase_repeated_hash(b: &mut ::test::Bencher) {
+        let x = UniCase::new(LONG);
+        b.iter(|| hash_of(&x));
+    }
+}
@@ -1,6 +1,7 @@
 #![cfg_attr(test, deny(missing_docs))]
 #![cfg_attr(test, deny(warnings))]
 #![cfg_attr(feature = "nightly", feature(test))]
+#![cfg_attr(__unicase__write_str_unstable, feature(hasher_prefixfree_extras))]
 #![no_std]
 
 //! # UniCase
@@ -41,26 +42,141 @@
 //!
 //! assert_eq!(a, b);
 //! ```
+//!
+//! ## Static maps
+//!
+//! [`phf`](https://docs.rs/phf), with its `unicase` feature, can build
+//! maps keyed on `UniCase` at compile time. Look keys up by wrapping them
+//! with `UniCase::new`. The `phf-compat` crate in the repository checks
+//! that this keeps working:
+//!
+//! ```rust,ignore
+//! use phf::phf_map;
+//! use unicase::UniCase;
+//!
+//! static METHODS: phf::Map<UniCase<&'static str>, u8> = phf_map! {
+//!     UniCase::ascii("GET") => 0,
+//!     UniCase::ascii("POST") => 1,
+//! };
+//!
+//! assert_eq!(METHODS.get(&UniCase::new("Post")), Some(&1));
+//! ```
 
-#[cfg(test)]
+#[cfg(any(test, feature = "std"))]
 extern crate std;
 #[cfg(feature = "nightly")]
 extern crate test;
 
 extern crate alloc;
+use alloc::boxed::Box;
 use alloc::string::String;
+use alloc::vec::Vec;
 
 use alloc::borrow::Cow;
 use core::cmp::Ordering;
 use core::fmt;
 use core::hash::{Hash, Hasher};
-use core::ops::{Deref, DerefMut};
+use core::mem;
+use core::ops::{
+    Deref, DerefMut, Index, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
+};
 use core::str::FromStr;
 
-use self::unicode::Unicode;
-
+use self::unicode::{lookup, Fold, Unicode};
+
+#[cfg(feature = "unicode-normalization")]
+pub use self::accent::AccentInsensitive;
+#[cfg(feature = "bstr")]
+pub use self::bstr::UniCaseBStr;
+pub use self::bytes::AsciiBytes;
+#[cfg(feature = "std")]
+pub use self::collections::{
+    group_by_ci, merge_ci, retain_unique_ci, UniCaseMapExt, UniCaseSetExt,
+};
+pub use self::config_key::ConfigKey;
+#[cfg(feature = "confusables")]
+pub use self::confusables::looks_like;
+pub use self::display::{DisplayFolded, DisplayOriginal};
+pub use self::dns::DnsName;
+#[cfg(feature = "std")]
+pub use self::fold_map::CaseFoldMap;
+#[cfg(__unicase__const_fold_hash)]
+pub use self::hash::const_fold_hash;
+#[cfg(feature = "identifier")]
+pub use self::identifier::Identifier;
+#[cfg(feature = "intern")]
+pub use self::intern::{CaseInsensitiveInterner, Symbol};
+pub use self::lazy::LazyFolded;
+#[cfg(feature = "std")]
+pub use self::lazy::SyncLazyFolded;
+#[doc(hidden)]
+pub use self::macros::__as_str;
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub use self::macros::__assert_ci_failed;
+#[cfg(__unicase__const_literal)]
+#[doc(hidden)]
+pub use self::macros::__literal;
+pub use self::no_opt::UniCaseNoOpt;
+pub use self::parse_ident::IdentError;
+pub use self::search::{common_prefix_ci, fold_words, levenshtein_ci};
+pub use self::slice::{dedup_sorted_ci, max_ci, min_ci, sort_by_folded_key};
+pub use self::small::SmallFolded;
+#[cfg(feature = "std")]
+pub use self::stream::streams_eq_ci;
+pub use self::vec_map::CaseInsensitiveVecMap;
+pub use self::writer::FoldWriter;
+
+#[macro_use]
+mod macros;
+
+#[cfg(feature = "unicode-normalization")]
+mod accent;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
 mod ascii;
+#[cfg(feature = "bstr")]
+#[clippy::msrv = "1.65"]
+mod bstr;
+mod bytes;
+#[cfg(feature = "std")]
+mod collections;
+mod config_key;
+#[cfg(feature = "confusables")]
+mod confusables;
+mod display;
+mod dns;
+#[cfg(feature = "std")]
+pub mod env;
+#[cfg(feature = "std")]
+mod fold_map;
+#[cfg(feature = "grapheme")]
+mod grapheme;
+mod hash;
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "identifier")]
+mod identifier;
+#[cfg(feature = "intern")]
+mod intern;
+mod lazy;
+mod no_opt;
+mod parse_ident;
+#[cfg(feature = "std")]
+mod path;
+#[cfg(feature = "regex")]
+mod regex;
+mod search;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod slice;
+mod small;
+#[cfg(feature = "std")]
+#[clippy::msrv = "1.70"]
+mod stream;
 mod unicode;
+mod vec_map;
+mod writer;
 
 /// Case Insensitive wrapper of strings.
 #[derive(Clone, Copy)]
@@ -89,6 +205,70 @@ pub fn eq_ascii<S: AsRef<str> + ?Sized>(left: &S, right: &S) -> bool {
     Ascii(left) == Ascii(right)
 }
 
+/// Compare two strings for case-less equality, ignoring ASCII case, in a
+/// `const` context.
+///
+/// This gives the same answer as [`eq_ascii`], but can run at compile time,
+/// for instance to check that a static table has no two keys that differ
+/// only in case.
+///
+/// Requires Rust 1.46 or newer.
+///
+/// # Example
+///
+/// ```rust
+/// use unicase::eq_ascii_const;
+///
+/// const HEADERS: &[&str] = &["Host", "Accept", "Content-Type"];
+///
+/// const fn has_duplicates(keys: &[&str]) -> bool {
+///     let mut i = 0;
+///     while i < keys.len() {
+///         let mut j = i + 1;
+///         while j < keys.len() {
+///             if eq_ascii_const(keys[i], keys[j]) {
+///                 return true;
+///             }
+///             j += 1;
+///         }
+///         i += 1;
+///     }
+///     false
+/// }
+///
+/// const _: () = assert!(!has_duplicates(HEADERS));
+/// ```
+#[cfg(__unicase__const_literal)]
+#[clippy::msrv = "1.46"]
+pub const fn eq_ascii_const(left: &str, right: &str) -> bool {
+    let (a, b) = (left.as_bytes(), right.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        // `u8::to_ascii_lowercase` is only `const` since 1.52
+        let (x, y) = (a[i] | ascii_case_bit(a[i]), b[i] | ascii_case_bit(b[i]));
+        if x != y {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// The bit that sets an ASCII uppercase letter to lowercase, or 0 for any
+/// other byte.
+#[cfg(__unicase__const_literal)]
+#[inline]
+const fn ascii_case_bit(b: u8) -> u8 {
+    if b >= b'A' && b <= b'Z' {
+        0x20
+    } else {
+        0
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 enum Encoding<S> {
     Ascii(Ascii<S>),
@@ -110,6 +290,35 @@ macro_rules! inner {
     }};
 }
 
+/// The case-folded chars of a string, in either folding mode.
+#[derive(Clone)]
+enum FoldedChars<'a> {
+    Ascii(core::str::Chars<'a>),
+    Unicode(core::iter::FlatMap<core::str::Chars<'a>, Fold, fn(char) -> Fold>),
+}
+
+impl<'a> FoldedChars<'a> {
+    fn new(s: &'a str, ascii: bool) -> FoldedChars<'a> {
+        if ascii {
+            FoldedChars::Ascii(s.chars())
+        } else {
+            FoldedChars::Unicode(s.chars().flat_map(lookup as fn(char) -> Fold))
+        }
+    }
+}
+
+impl<'a> Iterator for FoldedChars<'a> {
+    type Item = char;
+
+    #[inline]
+    fn next(&mut self) -> Option<char> {
+        match *self {
+            FoldedChars::Ascii(ref mut chars) => chars.next().map(|c| c.to_ascii_lowercase()),
+            FoldedChars::Unicode(ref mut chars) => chars.next(),
+        }
+    }
+}
+
 impl<S: AsRef<str> + Default> Default for UniCase<S> {
     fn default() -> Self {
         Self::new(Default::default())
@@ -128,6 +337,73 @@ impl<S: AsRef<str>> UniCase<S> {
         }
     }
 
+    /// Creates a new `UniCase`, or returns `None` if `s` is longer than
+    /// `max_len` bytes.
+    ///
+    /// This is meant for keys from untrusted input, to turn away overly long
+    /// ones before they are scanned, folded or hashed. The length is checked
+    /// first, so a rejected string is never scanned. A string of exactly
+    /// `max_len` bytes is accepted.
+    ///
+    /// The limit is on the original string: full case folding can make the
+    /// folded form up to three times as long.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// assert!(UniCase::new_bounded("Content-Type", 64).is_some());
+    /// assert!(UniCase::new_bounded("x".repeat(65), 64).is_none());
+    /// ```
+    #[inline]
+    pub fn new_bounded(s: S, max_len: usize) -> Option<UniCase<S>> {
+        if s.as_ref().len() > max_len {
+            None
+        } else {
+            Some(UniCase::new(s))
+        }
+    }
+
+    str_methods!();
+
+    /// Changes the inner value in place, then picks the folding mode again.
+    ///
+    /// Mutating through `DerefMut` keeps whatever mode the value was made
+    /// with, so writing non-ASCII text into an ASCII-mode value leaves it
+    /// reporting [`is_ascii`](UniCase::is_ascii), and writing ASCII text into
+    /// a Unicode-mode value misses the ASCII fast path. This runs `f`, then
+    /// rechecks the string exactly like [`UniCase::new`] does.
+    ///
+    /// `S` has to be `Default`, to hold the place of the string while the
+    /// mode changes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let mut name = UniCase::new(String::from("Anders"));
+    /// name.modify(|s| s.push_str(" Ångström"));
+    /// assert!(!name.is_ascii());
+    /// assert_eq!(name.to_folded_case(), "anders ångström");
+    /// ```
+    pub fn modify<F: FnOnce(&mut S)>(&mut self, f: F)
+    where
+        S: Default,
+    {
+        f(inner!(mut self.0));
+        let ascii = self.as_ref().is_ascii();
+        if ascii != self.is_ascii() {
+            let s = mem::replace(inner!(mut self.0), S::default());
+            *self = if ascii {
+                UniCase::ascii(s)
+            } else {
+                UniCase::unicode(s)
+            };
+        }
+    }
+
     /// Returns a copy of this string where each character is mapped to its
     /// Unicode CaseFolding equivalent.
     ///
@@ -136,10 +412,239 @@ impl<S: AsRef<str>> UniCase<S> {
     /// Unicode Case Folding is meant for string storage and matching, not for
     /// display.
     pub fn to_folded_case(&self) -> String {
+        if self.folds_as_ascii() {
+            self.as_ref().to_ascii_lowercase()
+        } else {
+            Unicode(self.as_ref()).to_folded_case()
+        }
+    }
+
+    /// Returns the case-folded form of this string, borrowing it if folding
+    /// would not change anything.
+    ///
+    /// The folding is the same as [`to_folded_case`](UniCase::to_folded_case),
+    /// but strings that are already folded, such as lowercase ASCII, are
+    /// returned as is, without allocating.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::borrow::Cow;
+    /// use unicase::UniCase;
+    ///
+    /// assert!(matches!(UniCase::new("content-type").to_folded(), Cow::Borrowed("content-type")));
+    /// assert_eq!(UniCase::new("Maße").to_folded(), "masse");
+    /// ```
+    pub fn to_folded(&self) -> Cow<'_, str> {
+        let s = self.as_ref();
+        // the byte index of the first char that folding changes
+        let ascii = self.folds_as_ascii();
+        let at = if ascii {
+            s.bytes().position(|b| b.is_ascii_uppercase())
+        } else {
+            s.char_indices()
+                .find(|&(_, c)| {
+                    let mut fold = lookup(c);
+                    fold.next() != Some(c) || fold.next().is_some()
+                })
+                .map(|(i, _)| i)
+        };
+        let at = match at {
+            Some(at) => at,
+            None => return Cow::Borrowed(s),
+        };
+        let mut folded = String::with_capacity(s.len());
+        folded.push_str(&s[..at]);
+        folded.extend(FoldedChars::new(&s[at..], ascii));
+        Cow::Owned(folded)
+    }
+
+    /// Returns a byte key that sorts the same way as `UniCase`'s `Ord`.
+    ///
+    /// The key is the UTF-8 encoding of the full Unicode case fold, which is
+    /// what `Ord` compares, whatever the folding mode. UTF-8 bytes sort in
+    /// the same order as the code points they encode, so for any `a` and
+    /// `b`:
+    ///
+    /// ```text
+    /// a.cmp(&b) == a.sort_key().cmp(&b.sort_key())
+    /// ```
+    ///
+    /// This makes it suitable for external sorted structures, such as FSTs
+    /// and on-disk indexes, that only compare bytes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// assert_eq!(UniCase::new("Maße").sort_key(), b"masse");
+    /// assert!(UniCase::new("apple").sort_key() < UniCase::new("Banana").sort_key());
+    /// ```
+    pub fn sort_key(&self) -> Vec<u8> {
+        FoldedChars::new(self.as_ref(), false)
+            .collect::<String>()
+            .into_bytes()
+    }
+
+    /// Returns an owned `UniCase` whose inner string is already case-folded.
+    ///
+    /// The result still compares and hashes like `self`, but its inner
+    /// string is [`to_folded_case`](UniCase::to_folded_case), which is handy
+    /// for storage. The original casing is lost. If the fold leaves only
+    /// ASCII, as `"Maße"` to `"masse"` does, the result uses the faster
+    /// ASCII comparisons.
+    ///
+    /// To keep the original string and cache the fold alongside it, see
+    /// [`LazyFolded`] instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let a = UniCase::new("Maße");
+    /// let b = a.canonicalize();
+    /// assert_eq!(b.as_ref(), "masse");
+    /// assert_eq!(a, b);
+    /// ```
+    pub fn canonicalize(&self) -> UniCase<String> {
+        if self.folds_as_ascii() {
+            UniCase::ascii(self.as_ref().to_ascii_lowercase())
+        } else {
+            UniCase::new(Unicode(self.as_ref()).to_folded_case())
+        }
+    }
+
+    /// Whether this value may be folded by ASCII folding alone, as
+    /// [`to_folded_case`](UniCase::to_folded_case) and friends do: it has to
+    /// be in ASCII mode, and really be ASCII, which [`UniCase::ascii`]
+    /// doesn't check. Otherwise they use the full fold, which `==` and
+    /// `Hash` go by.
+    #[inline]
+    fn folds_as_ascii(&self) -> bool {
+        self.is_ascii() && self.as_ref().is_ascii()
+    }
+
+    /// Returns an uppercased copy of this string, using ASCII or full
+    /// Unicode uppercasing depending on the mode of this `UniCase`.
+    ///
+    /// This is for systems whose canonical form is uppercase. Note that
+    /// uppercasing is not the inverse of case folding, and the two may not
+    /// round-trip: `"straße"` uppercases to `"STRASSE"` but folds to
+    /// `"strasse"`.
+    pub fn to_uppercase_string(&self) -> String {
         match self.0 {
-            Encoding::Ascii(ref s) => s.0.as_ref().to_ascii_lowercase(),
-            Encoding::Unicode(ref s) => s.to_folded_case(),
+            Encoding::Ascii(ref s) => s.0.as_ref().to_ascii_uppercase(),
+            Encoding::Unicode(ref s) => s.0.as_ref().to_uppercase(),
+        }
+    }
+
+    /// Returns a copy of this string with only ASCII letters uppercased.
+    pub fn to_ascii_uppercase_string(&self) -> String {
+        self.as_ref().to_ascii_uppercase()
+    }
+
+    /// Compares against any string-like value, ignoring case.
+    ///
+    /// This is what `==` does between two `UniCase`s, with `other` treated as
+    /// if it were wrapped with `UniCase::new`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let a = UniCase::new("Maße");
+    /// assert!(a.eq_ci("MASSE"));
+    /// assert!(a.eq_ci(String::from("masse")));
+    /// assert!(!a.eq_ci("mase"));
+    /// ```
+    #[inline]
+    pub fn eq_ci(&self, other: impl AsRef<str>) -> bool {
+        // comparing byte by byte falls back to the full fold wherever `other`
+        // isn't ASCII, so it can be treated as ASCII mode
+        self.eq_with_mode(other.as_ref(), true)
+    }
+
+    /// Compares against a stream of chars, ignoring case.
+    ///
+    /// This is for text that isn't stored as one `&str`, such as a rope,
+    /// without collecting it first. Both sides are folded as they are read,
+    /// and the stream is only consumed up to the first difference.
+    ///
+    /// Since the chars can't be checked for being all ASCII ahead of time,
+    /// this always uses full Unicode folding, which gives the same answer as
+    /// [`eq_ci`](UniCase::eq_ci).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let a = UniCase::new("Maße");
+    /// assert!(a.eq_chars("MASSE".chars()));
+    /// assert!(!a.eq_chars("MASS".chars()));
+    /// ```
+    pub fn eq_chars<I: IntoIterator<Item = char>>(&self, chars: I) -> bool {
+        let left = FoldedChars::new(self.as_ref(), false);
+        left.eq(chars.into_iter().flat_map(lookup))
+    }
+
+    /// Compares against a string, ignoring case and all whitespace.
+    ///
+    /// Whitespace is any char for which `char::is_whitespace` is true, and
+    /// it is skipped wherever it appears, so `"Foo Bar"` equals `"foobar"`
+    /// and `" foo\tbar "`. Otherwise this folds like
+    /// [`eq_ci`](UniCase::eq_ci).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let a = UniCase::new("Foo Bar");
+    /// assert!(a.eq_ignore_ws_ci("foobar"));
+    /// assert!(!a.eq_ignore_ws_ci("foo_bar"));
+    /// ```
+    pub fn eq_ignore_ws_ci(&self, other: &str) -> bool {
+        // no char folds to or from whitespace, so the folded chars can be
+        // filtered directly
+        let (left, right) = self.fold_pair(other);
+        left.filter(|c| !c.is_whitespace())
+            .eq(right.filter(|c| !c.is_whitespace()))
+    }
+
+    /// The one place that decides how two strings are compared for
+    /// equality, given whether `other` is in ASCII mode.
+    ///
+    /// Only the modes are checked: see [`cmp_ascii_first`] for how two
+    /// ASCII-mode strings are compared.
+    #[inline]
+    fn eq_with_mode(&self, other: &str, other_ascii: bool) -> bool {
+        let eq = match self.0 {
+            Encoding::Ascii(ref x) if other_ascii => {
+                cmp_ascii_first(x.as_ref(), other) == Ordering::Equal
+            }
+            _ => Unicode(self.as_ref()) == Unicode(other),
+        };
+        #[cfg(debug_assertions)]
+        {
+            if eq {
+                hash::debug_check_eq(self.as_ref(), other);
+            }
         }
+        eq
+    }
+
+    /// Folds `self` and `other` the same way comparing against
+    /// `UniCase::new(other)` would: ASCII folding only if both are ASCII.
+    fn fold_pair<'a>(&'a self, other: &'a str) -> (FoldedChars<'a>, FoldedChars<'a>) {
+        let ascii = ascii_fast_path(self.as_ref(), self.is_ascii(), other);
+        (
+            FoldedChars::new(self.as_ref(), ascii),
+            FoldedChars::new(other, ascii),
+        )
     }
 }
 
@@ -149,12 +654,21 @@ impl<S> UniCase<S> {
         UniCase(Encoding::Unicode(Unicode(s)))
     }
 
-    /// Creates a new `UniCase` which performs only ASCII case folding.
+    /// Creates a new `UniCase` which uses the faster ASCII case folding.
+    ///
+    /// This skips the check done by [`UniCase::new`], so it's meant for
+    /// strings already known to be ASCII, and is a `const fn`. Comparing two
+    /// ASCII-mode values only checks their modes, then goes a byte at a time.
+    ///
+    /// The mode is only an optimization, not a different relation: if the
+    /// string does hold non-ASCII text, that text is still compared, hashed
+    /// and folded with full Unicode folding, just like `UniCase::new` of the
+    /// same string. So `UniCase::ascii("Ä")` equals `UniCase::ascii("ä")`.
     pub const fn ascii(s: S) -> UniCase<S> {
         UniCase(Encoding::Ascii(Ascii(s)))
     }
 
-    /// Return `true` if this instance will only perform ASCII case folding.
+    /// Return `true` if this instance was created in ASCII mode.
     pub fn is_ascii(&self) -> bool {
         match self.0 {
             Encoding::Ascii(_) => true,
@@ -170,19 +684,346 @@ impl<S> UniCase<S> {
             Encoding::Unicode(s) => s.0,
         }
     }
+
+    /// Converts the inner value, keeping the folding mode as is.
+    fn map<T, F: FnOnce(S) -> T>(self, f: F) -> UniCase<T> {
+        match self.0 {
+            Encoding::Ascii(s) => UniCase(Encoding::Ascii(Ascii(f(s.0)))),
+            Encoding::Unicode(s) => UniCase(Encoding::Unicode(Unicode(f(s.0)))),
+        }
+    }
+}
+
+impl<'a> UniCase<&'a str> {
+    /// Returns a `UniCase` over a byte range of the original string.
+    ///
+    /// The slice keeps the folding mode of `self`, rather than checking its
+    /// own contents as [`UniCase::new`] would. So an ASCII-only slice of a
+    /// Unicode-mode string still compares with Unicode folding.
+    ///
+    /// # Panics
+    ///
+    /// Like slicing a `str`, this panics if the range is out of bounds or
+    /// does not fall on char boundaries.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let a = UniCase::new("Content-Type: text/html");
+    /// assert_eq!(a.slice(0..12), UniCase::new("content-type"));
+    /// ```
+    #[inline]
+    pub fn slice(&self, range: Range<usize>) -> UniCase<&'a str> {
+        self.map(|s| &s[range])
+    }
+
+    /// Splits the original string in two at a byte index.
+    ///
+    /// Unlike [`slice`](UniCase::slice), each half picks its own folding
+    /// mode, like [`UniCase::new`]: splitting `"abc-Maße"` gives an ASCII
+    /// half and a Unicode half.
+    ///
+    /// # Panics
+    ///
+    /// Like `str::split_at`, this panics if `mid` is out of bounds or not on
+    /// a char boundary.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let (a, b) = UniCase::new("abc-Maße").split_at(4);
+    /// assert!(a.is_ascii());
+    /// assert!(!b.is_ascii());
+    /// assert_eq!(b, UniCase::new("MASSE"));
+    /// ```
+    #[inline]
+    pub fn split_at(&self, mid: usize) -> (UniCase<&'a str>, UniCase<&'a str>) {
+        let (a, b) = self.into_inner().split_at(mid);
+        (UniCase::new(a), UniCase::new(b))
+    }
+
+    /// Iterates over the lines of the original string, as `UniCase`s.
+    ///
+    /// Lines are split like `str::lines` does, on `\n` or `\r\n`, without
+    /// the line ending. Like [`split_at`](UniCase::split_at), each line
+    /// picks its own folding mode, so ASCII lines keep the fast comparison
+    /// even if other lines are not ASCII. The lines borrow the same string
+    /// as `self`, and can outlive it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let log = UniCase::new("INFO started\nERROR Disk Full\nwarn Straße");
+    /// let errors = log
+    ///     .lines_ci()
+    ///     .filter(|line| line.eq_ci("error disk full"))
+    ///     .count();
+    /// assert_eq!(errors, 1);
+    /// ```
+    #[inline]
+    pub fn lines_ci(&self) -> impl Iterator<Item = UniCase<&'a str>> {
+        self.into_inner().lines().map(UniCase::new)
+    }
+
+    /// Returns the original string with leading and trailing whitespace
+    /// removed, as a `UniCase`.
+    ///
+    /// Whitespace is as defined by `char::is_whitespace`, like `str::trim`.
+    /// The trimmed string picks its own folding mode, like
+    /// [`split_at`](UniCase::split_at), so non-ASCII whitespace around an
+    /// ASCII key doesn't leave it on the slower Unicode path.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let key = UniCase::new("  Content-Type\t");
+    /// assert_eq!(key.trim().as_ref(), "Content-Type");
+    /// assert_eq!(key.trim(), UniCase::new("content-type"));
+    /// ```
+    #[inline]
+    pub fn trim(&self) -> UniCase<&'a str> {
+        UniCase::new(self.into_inner().trim())
+    }
+
+    /// Returns the original string with leading whitespace removed, as a
+    /// `UniCase`.
+    ///
+    /// Like [`trim`](UniCase::trim), the folding mode is picked again.
+    #[inline]
+    pub fn trim_start(&self) -> UniCase<&'a str> {
+        UniCase::new(self.into_inner().trim_start())
+    }
+
+    /// Returns the original string with trailing whitespace removed, as a
+    /// `UniCase`.
+    ///
+    /// Like [`trim`](UniCase::trim), the folding mode is picked again.
+    #[inline]
+    pub fn trim_end(&self) -> UniCase<&'a str> {
+        UniCase::new(self.into_inner().trim_end())
+    }
+
+    /// Creates a new `UniCase`, after removing invisible format characters
+    /// from both ends of `s`.
+    ///
+    /// Text pasted from documents and web pages often carries these, which
+    /// makes `"\u{FEFF}Foo"` not match `"foo"` although the two look the
+    /// same. Exactly these code points are removed, however many there are:
+    ///
+    /// - U+FEFF ZERO WIDTH NO-BREAK SPACE, the byte order mark
+    /// - U+200B ZERO WIDTH SPACE
+    /// - U+200C ZERO WIDTH NON-JOINER
+    /// - U+200D ZERO WIDTH JOINER
+    /// - U+200E LEFT-TO-RIGHT MARK
+    /// - U+200F RIGHT-TO-LEFT MARK
+    /// - U+2060 WORD JOINER
+    ///
+    /// Whitespace is kept, see [`trim`](UniCase::trim) for that, and so are
+    /// format characters in the middle of the string. To ignore those as
+    /// well, use `Identifier`, with the `identifier` feature.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let key = UniCase::new_trim_format("\u{FEFF}Foo\u{200B}");
+    /// assert_eq!(key.as_ref(), "Foo");
+    /// assert_eq!(key, UniCase::new("foo"));
+    /// ```
+    #[inline]
+    pub fn new_trim_format(s: &'a str) -> UniCase<&'a str> {
+        UniCase::new(s.trim_matches(is_edge_format))
+    }
+}
+
+/// The invisible format characters [`UniCase::new_trim_format`] removes.
+fn is_edge_format(c: char) -> bool {
+    ('\u{200B}'..='\u{200F}').contains(&c) || c == '\u{2060}' || c == '\u{FEFF}'
+}
+
+impl UniCase<String> {
+    /// Returns the capacity of the inner `String`, in bytes.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        inner!(self.0).capacity()
+    }
+
+    /// Reserves capacity for at least `additional` more bytes in the inner
+    /// `String`.
+    ///
+    /// This only touches the allocation, so the folding mode is kept as is.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        inner!(mut self.0).reserve(additional)
+    }
+
+    /// Shrinks the capacity of the inner `String` to match its length.
+    ///
+    /// This only touches the allocation, so the folding mode is kept as is.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        inner!(mut self.0).shrink_to_fit()
+    }
+
+    /// Appends the case-folded form of `s` to the inner string.
+    ///
+    /// Unlike `push_str` through `DerefMut`, which appends `s` as written,
+    /// this stores what [`to_folded_case`](UniCase::to_folded_case) would
+    /// return for `UniCase::new(s)`. Building a key only from folded pieces
+    /// leaves its inner string already canonical, with no separate folding
+    /// pass. Comparisons are unaffected: the value still compares and hashes
+    /// case-insensitively, exactly as if `s` had been pushed unfolded.
+    ///
+    /// Afterwards the folding mode is picked again, like
+    /// [`modify`](UniCase::modify) does, so appending non-ASCII text
+    /// switches to Unicode folding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let mut key = UniCase::new(String::new());
+    /// key.push_folded("Content-");
+    /// key.push_folded("TYPE");
+    /// assert_eq!(key.as_ref(), "content-type");
+    /// assert_eq!(key, UniCase::new("Content-Type"));
+    /// ```
+    pub fn push_folded(&mut self, s: &str) {
+        self.modify(|inner| inner.extend(FoldedChars::new(s, s.is_ascii())));
+    }
+
+    /// Shortens the inner string to `new_len` bytes, then picks the folding
+    /// mode again.
+    ///
+    /// Cutting off the non-ASCII tail of a string lets it use the faster
+    /// ASCII comparisons again, which `String::truncate` through `DerefMut`
+    /// would not. Like [`modify`](UniCase::modify), this rechecks the string
+    /// exactly as [`UniCase::new`] does. If `new_len` is not less than the
+    /// current length, nothing changes.
+    ///
+    /// # Panics
+    ///
+    /// Like `String::truncate`, this panics if `new_len` is not on a char
+    /// boundary.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let mut s = UniCase::new(String::from("café"));
+    /// s.truncate(3);
+    /// assert!(s.is_ascii());
+    /// assert_eq!(s, UniCase::new("CAF"));
+    /// ```
+    #[inline]
+    pub fn truncate(&mut self, new_len: usize) {
+        self.modify(|inner| inner.truncate(new_len));
+    }
+
+    /// Inserts a char at byte position `idx`, then picks the folding mode
+    /// again.
+    ///
+    /// Unlike `String::insert` through `DerefMut`, inserting a non-ASCII
+    /// char into an ASCII-mode value switches it to Unicode folding, like
+    /// [`modify`](UniCase::modify) does.
+    ///
+    /// # Panics
+    ///
+    /// Like `String::insert`, this panics if `idx` is larger than the
+    /// length, or not on a char boundary.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let mut s = UniCase::new(String::from("Strae"));
+    /// s.insert(4, 'ß');
+    /// assert!(!s.is_ascii());
+    /// assert_eq!(s, UniCase::new("STRASSE"));
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, idx: usize, c: char) {
+        self.modify(|inner| inner.insert(idx, c));
+    }
+
+    /// Inserts a string slice at byte position `idx`, then picks the
+    /// folding mode again.
+    ///
+    /// Like [`insert`](UniCase::insert), inserting non-ASCII text into an
+    /// ASCII-mode value switches it to Unicode folding.
+    ///
+    /// # Panics
+    ///
+    /// Like `String::insert_str`, this panics if `idx` is larger than the
+    /// length, or not on a char boundary.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let mut s = UniCase::new(String::from("Anders "));
+    /// s.insert_str(0, "Ångström, ");
+    /// assert!(!s.is_ascii());
+    /// assert_eq!(s.to_folded_case(), "ångström, anders ");
+    /// ```
+    #[inline]
+    pub fn insert_str(&mut self, idx: usize, s: &str) {
+        self.modify(|inner| inner.insert_str(idx, s));
+    }
+}
+
+impl<S: Into<Box<str>>> UniCase<S> {
+    /// Converts the inner string into a `Box<str>`, keeping its original
+    /// casing.
+    ///
+    /// A `String` with no spare capacity is converted without copying.
+    #[inline]
+    pub fn into_boxed_str(self) -> Box<str> {
+        self.into_inner().into()
+    }
+}
+
+impl<S: AsRef<str>> UniCase<S> {
+    /// Returns the case-folded form of this string as a `Box<str>`.
+    ///
+    /// The folding is the same as [`to_folded_case`](UniCase::to_folded_case).
+    /// This is a compact form for interned keys, where the original casing
+    /// is no longer needed.
+    #[inline]
+    pub fn into_folded_boxed_str(self) -> Box<str> {
+        self.to_folded_case().into_boxed_str()
+    }
 }
 
 impl<S> Deref for UniCase<S> {
     type Target = S;
     #[inline]
-    fn deref<'a>(&'a self) -> &'a S {
+    fn deref(&self) -> &S {
         inner!(self.0)
     }
 }
 
+/// Gives mutable access to the inner value.
+///
+/// The folding mode is not updated afterwards: a value made in ASCII mode
+/// stays in it even if non-ASCII text is written. Use
+/// [`UniCase::modify`] to have the mode picked again.
 impl<S> DerefMut for UniCase<S> {
     #[inline]
-    fn deref_mut<'a>(&'a mut self) -> &'a mut S {
+    fn deref_mut(&mut self) -> &mut S {
         inner!(mut self.0)
     }
 }
@@ -194,6 +1035,34 @@ impl<S: AsRef<str>> AsRef<str> for UniCase<S> {
     }
 }
 
+macro_rules! index_impl {
+    ($($range:ty),+) => {
+        $(
+            /// Slices the original string, like indexing a `str`.
+            ///
+            /// The slice keeps its original casing; it is not folded. Like
+            /// `str`, this panics if the range is out of bounds or does not
+            /// fall on char boundaries.
+            impl<S: AsRef<str>> Index<$range> for UniCase<S> {
+                type Output = str;
+                #[inline]
+                fn index(&self, index: $range) -> &str {
+                    &self.as_ref()[index]
+                }
+            }
+        )+
+    };
+}
+
+index_impl!(
+    Range<usize>,
+    RangeFrom<usize>,
+    RangeTo<usize>,
+    RangeFull,
+    RangeInclusive<usize>,
+    RangeToInclusive<usize>
+);
+
 impl<S: fmt::Debug> fmt::Debug for UniCase<S> {
     #[inline]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
@@ -201,6 +1070,9 @@ impl<S: fmt::Debug> fmt::Debug for UniCase<S> {
     }
 }
 
+/// Formats the original string, passing the formatter and its flags on to
+/// the inner value. So `&str` and `String` honor width, fill, alignment and
+/// precision, exactly as they would unwrapped.
 impl<S: fmt::Display> fmt::Display for UniCase<S> {
     #[inline]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
@@ -211,24 +1083,55 @@ impl<S: fmt::Display> fmt::Display for UniCase<S> {
 impl<S1: AsRef<str>, S2: AsRef<str>> PartialEq<UniCase<S2>> for UniCase<S1> {
     #[inline]
     fn eq(&self, other: &UniCase<S2>) -> bool {
-        match (&self.0, &other.0) {
-            (&Encoding::Ascii(ref x), &Encoding::Ascii(ref y)) => x == y,
-            (&Encoding::Unicode(ref x), &Encoding::Unicode(ref y)) => x == y,
-            (&Encoding::Ascii(ref x), &Encoding::Unicode(ref y)) => &Unicode(x.as_ref()) == y,
-            (&Encoding::Unicode(ref x), &Encoding::Ascii(ref y)) => x == &Unicode(y.as_ref()),
-        }
+        self.eq_with_mode(other.as_ref(), other.is_ascii())
     }
 }
 
 impl<S: AsRef<str>> Eq for UniCase<S> {}
 
+/// Compares against a single char, ignoring case.
+///
+/// This is `true` only if the string is exactly one char, and that char
+/// folds the same as `other`, following the same folding rules as `==`
+/// between two `UniCase`s. Since both sides must be a single char, a fold
+/// that expands is only equal to itself: `"ß" == 'ß'`, but `"ss" != 'ß'`,
+/// even though `UniCase::new("ss") == UniCase::new("ß")`.
+impl<S: AsRef<str>> PartialEq<char> for UniCase<S> {
+    #[inline]
+    fn eq(&self, other: &char) -> bool {
+        let mut chars = self.as_ref().chars();
+        if chars.next().is_none() || chars.next().is_some() {
+            return false;
+        }
+        let mut buf = [0; 4];
+        self.eq_with_mode(other.encode_utf8(&mut buf), true)
+    }
+}
+
+impl<S: AsRef<str>> PartialEq<UniCase<S>> for char {
+    #[inline]
+    fn eq(&self, other: &UniCase<S>) -> bool {
+        other == self
+    }
+}
+
+/// Hashes the case-folded string.
+///
+/// The stream fed to the hasher is the UTF-8 bytes of the full Unicode case
+/// fold, whatever the folding mode, followed by a single `0xFF` byte. This
+/// is the same stream `str` itself would write for the folded string, and
+/// every wrapper in this crate that compares like `UniCase` writes it too.
+///
+/// `0xFF` never occurs in UTF-8, so it marks the end of the string
+/// unambiguously: hashing several values in a row, as a tuple or slice of
+/// `UniCase`s does, can't make `("ab", "c")` and `("a", "bc")` feed the
+/// same stream. Any key type made of several strings should write each one
+/// like this, rather than their concatenation.
 impl<S: AsRef<str>> Hash for UniCase<S> {
     #[inline]
     fn hash<H: Hasher>(&self, hasher: &mut H) {
-        match self.0 {
-            Encoding::Ascii(ref s) => s.hash(hasher),
-            Encoding::Unicode(ref s) => s.hash(hasher),
-        }
+        // one stream for both modes, see `hash::fold_hash`
+        hash::fold_hash(self.as_ref(), hasher)
     }
 }
 
@@ -238,6 +1141,14 @@ impl<S> From<Ascii<S>> for UniCase<S> {
     }
 }
 
+/// Clones the string, keeping the folding mode of the borrowed value instead
+/// of detecting it again.
+impl<'a> From<UniCase<&'a str>> for UniCase<String> {
+    fn from(s: UniCase<&'a str>) -> Self {
+        s.map(String::from)
+    }
+}
+
 macro_rules! from_impl {
     ($from:ty => $to:ty; $by:ident) => (
         impl<'a> From<$from> for UniCase<$to> {
@@ -251,9 +1162,9 @@ macro_rules! from_impl {
 
 macro_rules! into_impl {
     ($to:ty) => {
-        impl<'a> Into<$to> for UniCase<$to> {
-            fn into(self) -> $to {
-                self.into_inner()
+        impl<'a> From<UniCase<$to>> for $to {
+            fn from(s: UniCase<$to>) -> Self {
+                s.into_inner()
             }
         }
     };
@@ -282,22 +1193,65 @@ impl<T: AsRef<str>> PartialOrd for UniCase<T> {
     }
 }
 
+/// Orders by the full Unicode case fold, whatever the mode of either side.
+///
+/// This is the same relation as `==`, and like it, compares two ASCII-mode
+/// values a byte at a time, as long as that gives the same answer.
 impl<T: AsRef<str>> Ord for UniCase<T> {
     #[inline]
     fn cmp(&self, other: &Self) -> Ordering {
         match (&self.0, &other.0) {
-            (&Encoding::Ascii(ref x), &Encoding::Ascii(ref y)) => x.cmp(y),
-            (&Encoding::Unicode(ref x), &Encoding::Unicode(ref y)) => x.cmp(y),
-            (&Encoding::Ascii(ref x), &Encoding::Unicode(ref y)) => {
-                Unicode(x.as_ref()).cmp(&Unicode(y.0.as_ref()))
-            }
-            (&Encoding::Unicode(ref x), &Encoding::Ascii(ref y)) => {
-                Unicode(x.0.as_ref()).cmp(&Unicode(y.as_ref()))
-            }
+            (Encoding::Ascii(x), Encoding::Ascii(y)) => cmp_ascii_first(x.as_ref(), y.as_ref()),
+            _ => Unicode(self.as_ref()).cmp(&Unicode(other.as_ref())),
         }
     }
 }
 
+/// Compares the full case folds of `a` and `b`, a byte at a time for as long
+/// as they only differ in ASCII case.
+///
+/// This is how two values in ASCII mode are compared, without checking
+/// first that they really are ASCII: [`UniCase::ascii`] is a `const fn`, so
+/// it can't look at its input. While both strings are ASCII, this is plain
+/// ASCII folding. At the first difference that involves a non-ASCII byte, it
+/// falls back to the full fold from the char that byte is in: up to there
+/// both strings hold the same chars but for ASCII case, so they fold the
+/// same and have the same char boundaries.
+#[inline]
+fn cmp_ascii_first(a: &str, b: &str) -> Ordering {
+    let (x, y) = (a.as_bytes(), b.as_bytes());
+    for (i, (&p, &q)) in x.iter().zip(y).enumerate() {
+        let (lp, lq) = (p.to_ascii_lowercase(), q.to_ascii_lowercase());
+        if lp == lq {
+            continue;
+        }
+        if p.is_ascii() && q.is_ascii() {
+            return lp.cmp(&lq);
+        }
+        let mut at = i;
+        while !a.is_char_boundary(at) {
+            at -= 1;
+        }
+        return Unicode(&a[at..]).cmp(&Unicode(&b[at..]));
+    }
+    // one is a prefix of the other, and no char folds to nothing
+    x.len().cmp(&y.len())
+}
+
+/// Whether two strings, one in ASCII mode and the other in ASCII mode if
+/// `other_ascii`, may be folded by ASCII folding alone, as the searching
+/// helpers do.
+///
+/// The mode is only a hint, since [`UniCase::ascii`] doesn't check its
+/// input, so both strings must also really be ASCII. Then ASCII folding
+/// gives the same answer as the full fold.
+#[inline]
+fn ascii_fast_path(s: &str, other_ascii: bool, other: &str) -> bool {
+    other_ascii && s.is_ascii() && other.is_ascii()
+}
+
+/// Parses `S`, then wraps it with [`UniCase::new`], so the mode is detected
+/// just as it is for any other `UniCase`.
 impl<S: FromStr + AsRef<str>> FromStr for UniCase<S> {
     type Err = <S as FromStr>::Err;
     fn from_str(s: &str) -> Result<UniCase<S>, Self::Err> {
@@ -307,17 +1261,28 @@ impl<S: FromStr + AsRef<str>> FromStr for UniCase<S> {
 
 #[cfg(test)]
 mod tests {
-    use super::UniCase;
+    use super::{Ascii, UniCase, UniCaseNoOpt};
+    use crate::hash::hash_of;
     use alloc::borrow::Cow;
+    use core::cmp::Ordering;
     use std::borrow::ToOwned;
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
+    use std::format;
     use std::string::String;
+    use std::vec::Vec;
 
-    fn hash<T: Hash>(t: &T) -> u64 {
-        let mut s = DefaultHasher::new();
-        t.hash(&mut s);
-        s.finish()
+    #[test]
+    fn test_display_padding() {
+        let a = UniCase::new("Maße");
+        assert_eq!(format!("{}|", a), "Maße|");
+        assert_eq!(format!("{:>6}|", a), "  Maße|");
+        assert_eq!(format!("{:*<6}|", a), "Maße**|");
+        assert_eq!(
+            format!("{:^7}|", UniCase::new(String::from("Host"))),
+            " Host  |"
+        );
+        assert_eq!(format!("{:.3}|", a), "Maß|");
+        assert_eq!(format!("{:>5.2}|", UniCase::ascii("Accept")), "   Ac|");
+        assert_eq!(format!("{:2}|", a), "Maße|");
     }
 
     #[test]
@@ -329,6 +1294,31 @@ mod tests {
         foo(a);
     }
 
+    #[cfg(__unicase__const_literal)]
+    #[test]
+    fn test_eq_ascii_const() {
+        use super::{eq_ascii, eq_ascii_const, Ascii};
+
+        // fails to compile unless it holds; `assert!` in a const needs 1.57
+        const _: [(); 1] = [(); eq_ascii_const("Foo", "foo") as usize];
+        const _: [(); 0] = [(); eq_ascii_const("Foo", "fop") as usize];
+        const HOST: Ascii<&str> = Ascii::new("HOST");
+        const _: [(); 1] = [(); HOST.eq_const(&Ascii::new("host")) as usize];
+
+        let bytes: Vec<u8> = (0..=0x7F).collect();
+        let all = core::str::from_utf8(&bytes).unwrap();
+        for (i, c) in all.char_indices() {
+            for (j, d) in all.char_indices() {
+                let (a, b) = (&all[i..=i], &all[j..=j]);
+                assert_eq!(eq_ascii_const(a, b), eq_ascii(a, b), "{:?} {:?}", c, d);
+            }
+        }
+        assert!(eq_ascii_const("Maße", "MAßE"));
+        assert!(!eq_ascii_const("Maße", "MASSE"));
+        assert!(!eq_ascii_const("ab", "abc"));
+        assert!(eq_ascii_const("", ""));
+    }
+
     #[test]
     fn test_eq_ascii() {
         let a = UniCase::new("foobar");
@@ -352,9 +1342,9 @@ mod tests {
         assert_eq!(d, b);
         assert_eq!(d, c);
 
-        assert_eq!(hash(&a), hash(&b));
-        assert_eq!(hash(&a), hash(&c));
-        assert_eq!(hash(&a), hash(&d));
+        assert_eq!(hash_of(&a), hash_of(&b));
+        assert_eq!(hash_of(&a), hash_of(&c));
+        assert_eq!(hash_of(&a), hash_of(&d));
 
         assert!(a.is_ascii());
         assert!(b.is_ascii());
@@ -383,7 +1373,26 @@ mod tests {
         let b = UniCase::new("στιγμασ");
         assert_eq!(a, b);
         assert_eq!(b, a);
-        assert_eq!(hash(&a), hash(&b));
+        assert_eq!(hash_of(&a), hash_of(&b));
+    }
+
+    #[test]
+    fn test_eq_ci() {
+        let a = UniCase::new("FooBar");
+        assert!(a.eq_ci("foobar"));
+        assert!(a.eq_ci(String::from("FOOBAR")));
+        assert!(a.eq_ci(Cow::from("fOObAR")));
+        assert!(!a.eq_ci("foo"));
+
+        let b = UniCase::new("Maße");
+        assert!(b.eq_ci("MASSE"));
+        assert!(b.eq_ci(String::from("masse").as_str()));
+        assert!(b.eq_ci(Cow::from("MAẞE")));
+
+        // a forced ASCII value still uses Unicode folding against non-ASCII
+        assert!(UniCase::ascii("masse").eq_ci("Maße"));
+        assert!(UniCase::ascii("Ä").eq_ci("ä"));
+        assert!(!UniCase::ascii("Ä").eq_ci("a"));
     }
 
     #[test]
@@ -406,7 +1415,7 @@ mod tests {
     }
 
     #[cfg(feature = "nightly")]
-    static SUBJECT: &'static [u8] = b"ffoo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz oo bar baz quux herp derp";
+    static SUBJECT: &[u8] = b"ffoo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz foo bar baz oo bar baz quux herp derp";
 
     #[cfg(feature = "nightly")]
     #[inline(never)]
@@ -439,6 +1448,146 @@ mod tests {
         assert!(UniCase::new("a") < UniCase::new("AA"));
     }
 
+    #[test]
+    fn test_case_cmp_mixed_modes() {
+        // xorshift64, so the test is reproducible
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as usize
+        };
+        let alphabet = ['a', 'A', 'ä', 'Ä', 's', 'S', 'ß', 'k', 'K', '\u{212A}'];
+
+        for _ in 0..200 {
+            let mut v: Vec<UniCase<String>> = (0..20)
+                .map(|_| {
+                    let len = next() % 4;
+                    let s: String = (0..len)
+                        .map(|_| alphabet[next() % alphabet.len()])
+                        .collect();
+                    match next() % 3 {
+                        0 => UniCase::new(s),
+                        1 => UniCase::ascii(s),
+                        _ => UniCase::unicode(s),
+                    }
+                })
+                .collect();
+            v.sort();
+
+            for i in 0..v.len() {
+                for j in i..v.len() {
+                    assert!(v[i] <= v[j], "{:?} > {:?}", v[i], v[j]);
+                    assert_eq!(v[i].cmp(&v[j]), v[j].cmp(&v[i]).reverse());
+                    assert_eq!(v[i].cmp(&v[j]) == Ordering::Equal, v[i] == v[j]);
+                }
+            }
+        }
+
+        // a forced ASCII value sorts by its full fold
+        assert_eq!(UniCase::ascii("Ä").cmp(&UniCase::new("ä")), Ordering::Equal);
+        assert_eq!(
+            UniCase::ascii("ß").cmp(&UniCase::new("SS")),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_only_case_is_ignored() {
+        // the looser relations of `ConfigKey`, `DnsName`, `Identifier` and
+        // `AccentInsensitive` stay in their own types
+        assert!(UniCase::new("Max-Size") != UniCase::new("max_size"));
+        assert!(UniCase::new("example.com.") != UniCase::new("example.com"));
+        assert!(UniCase::new("a\u{200B}b") != UniCase::new("ab"));
+        assert!(UniCase::new("café") != UniCase::new("cafe"));
+        assert_eq!(UniCase::new("café"), UniCase::new("CAFÉ"));
+        assert!(!crate::eq("max-size", "max_size"));
+        assert!(crate::eq_ascii("café", "CAFé"));
+        assert!(!crate::eq_ascii("café", "cafe"));
+    }
+
+    #[test]
+    fn test_forced_ascii_non_ascii_text() {
+        // the mode is only a hint, so `==`, `cmp` and `Hash` all fall back to
+        // the full fold, and agree with each other
+        let pairs = [
+            (UniCase::ascii("Ä"), UniCase::ascii("ä")),
+            (UniCase::ascii("ß"), UniCase::new("SS")),
+            (UniCase::ascii("\u{212A}"), UniCase::ascii("k")),
+            (UniCase::ascii("Maße"), UniCase::unicode("MASSE")),
+        ];
+        for &(a, b) in &pairs {
+            assert_eq!(a, b);
+            assert_eq!(a.cmp(&b), Ordering::Equal, "{:?} vs {:?}", a, b);
+            assert_eq!(hash_of(&a), hash_of(&b));
+        }
+        assert!(UniCase::ascii("Ä") != UniCase::ascii("a"));
+        assert!(UniCase::ascii("Ä") > UniCase::ascii("a"));
+    }
+
+    #[test]
+    fn test_sort_key_matches_ord() {
+        // xorshift64, so the test is reproducible
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as usize
+        };
+        let alphabet = [
+            'a',
+            'B',
+            'z',
+            '~',
+            'ä',
+            'Ä',
+            's',
+            'S',
+            'ß',
+            'ẞ',
+            '\u{212A}',
+            'Σ',
+            'ς',
+            'ﬃ',
+            '\u{10400}',
+            '\u{FFFD}',
+        ];
+        let mut random = || -> UniCase<String> {
+            let len = next() % 5;
+            let s: String = (0..len)
+                .map(|_| alphabet[next() % alphabet.len()])
+                .collect();
+            match next() % 3 {
+                0 => UniCase::new(s),
+                1 => UniCase::ascii(s),
+                _ => UniCase::unicode(s),
+            }
+        };
+
+        for _ in 0..5000 {
+            let a = random();
+            let b = random();
+            assert_eq!(
+                a.cmp(&b),
+                a.sort_key().cmp(&b.sort_key()),
+                "{:?} vs {:?}",
+                a,
+                b
+            );
+            assert_eq!(a == b, a.cmp(&b) == Ordering::Equal, "{:?} vs {:?}", a, b);
+            // equal values, in whatever modes, fold to the same string, so
+            // a folded copy finds the other
+            if a == b {
+                assert_eq!(a.to_folded_case(), b.to_folded_case());
+                assert_eq!(a.to_folded(), b.to_folded());
+                assert_eq!(a.canonicalize().as_ref(), b.canonicalize().as_ref());
+            }
+        }
+        assert_eq!(UniCase::ascii("Ä").sort_key(), "ä".as_bytes());
+    }
+
     #[test]
     fn test_from_impls() {
         let view: &'static str = "foobar";
@@ -451,6 +1600,35 @@ mod tests {
         let _: UniCase<String> = owned.into();
     }
 
+    #[test]
+    fn test_from_borrowed_keeps_mode() {
+        let forced: UniCase<String> = UniCase::ascii("Ä").into();
+        assert!(forced.is_ascii());
+        assert_eq!(forced.as_ref(), "Ä");
+
+        let forced: UniCase<String> = UniCase::unicode("foo").into();
+        assert!(!forced.is_ascii());
+
+        let detected: UniCase<String> = UniCase::new("Maße").into();
+        assert!(!detected.is_ascii());
+        assert_eq!(detected, UniCase::new("MASSE"));
+    }
+
+    #[test]
+    fn test_from_str_detects_mode() {
+        for &s in &["Åström", "foobar"] {
+            let parsed: UniCase<String> = s.parse().unwrap();
+            let built = UniCase::new(s.to_owned());
+            assert_eq!(parsed.is_ascii(), built.is_ascii());
+            assert_eq!(parsed, built);
+            assert_eq!(hash_of(&parsed), hash_of(&built));
+        }
+
+        let parsed: UniCase<String> = "Åström".parse().unwrap();
+        assert!(!parsed.is_ascii());
+        assert_eq!(parsed, UniCase::new("åSTRÖM"));
+    }
+
     #[test]
     fn test_into_impls() {
         let view: UniCase<&'static str> = UniCase::new("foobar");
@@ -462,6 +1640,451 @@ mod tests {
         let _: &str = owned.as_ref();
     }
 
+    #[test]
+    fn test_canonicalize() {
+        for &s in &["FooBar", "Maße", "ΣΊΣΥΦΟΣ", "ǅemal"] {
+            let a = UniCase::new(s);
+            let b = a.canonicalize();
+            assert_eq!(b.as_ref(), a.to_folded_case());
+            assert_eq!(a, b);
+            assert_eq!(hash_of(&a), hash_of(&b));
+            assert_eq!(b.canonicalize(), b);
+            assert_eq!(b.canonicalize().as_ref(), b.as_ref());
+        }
+
+        // folds down to ASCII, so it can use the ASCII fast path
+        assert!(UniCase::new("Maße").canonicalize().is_ascii());
+        assert!(!UniCase::new("Σ").canonicalize().is_ascii());
+
+        // forced ASCII mode stays ASCII for ASCII text, and otherwise gets
+        // the full fold, which `==` goes by
+        let forced = UniCase::ascii("FooBar").canonicalize();
+        assert!(forced.is_ascii());
+        assert_eq!(forced.as_ref(), "foobar");
+        let forced = UniCase::ascii("ÄB").canonicalize();
+        assert!(!forced.is_ascii());
+        assert_eq!(forced.as_ref(), "äb");
+    }
+
+    #[test]
+    fn test_string_capacity() {
+        let mut a = UniCase::new(String::from("Maße"));
+        let before = hash_of(&a);
+
+        a.reserve(100);
+        assert!(a.capacity() >= "Maße".len() + 100);
+        a.shrink_to_fit();
+        assert!(a.capacity() < 100);
+
+        assert!(!a.is_ascii());
+        assert_eq!(a, UniCase::new("MASSE"));
+        assert_eq!(hash_of(&a), before);
+
+        let mut b = UniCase::ascii(String::from("Ä"));
+        b.reserve(10);
+        b.shrink_to_fit();
+        assert!(b.is_ascii());
+    }
+
+    #[test]
+    fn test_push_folded() {
+        let mut a = UniCase::new(String::from("Key:"));
+        a.push_folded("Maße");
+        assert_eq!(a.as_ref(), "Key:masse");
+        assert!(a.is_ascii());
+        assert_eq!(a, UniCase::new("KEY:MASSE"));
+
+        a.push_folded("ΣΊΣΥΦΟΣ");
+        assert_eq!(a.as_ref(), "Key:masseσίσυφοσ");
+        assert!(!a.is_ascii());
+        assert_eq!(a, UniCase::new("key:MASSEσίσυφος"));
+        assert_eq!(hash_of(&a), hash_of(&UniCase::new("key:MASSEσίσυφος")));
+
+        // only the pushed text is folded, and ASCII mode folds it fully
+        let mut b = UniCase::ascii(String::from("Ä"));
+        b.push_folded("É");
+        assert_eq!(b.as_ref(), "Äé");
+        assert!(!b.is_ascii());
+    }
+
+    #[test]
+    fn test_new_trim_format() {
+        let key = UniCase::new_trim_format("\u{FEFF}Foo");
+        assert_eq!(key.as_ref(), "Foo");
+        assert_eq!(key, UniCase::new("foo"));
+        assert!(key.is_ascii());
+        assert!(UniCase::new("\u{FEFF}Foo") != UniCase::new("foo"));
+
+        for &c in &[
+            '\u{FEFF}', '\u{200B}', '\u{200C}', '\u{200D}', '\u{200E}', '\u{200F}', '\u{2060}',
+        ] {
+            let mut s = String::new();
+            s.push(c);
+            s.push_str("Maße");
+            s.push(c);
+            s.push(c);
+            assert_eq!(UniCase::new_trim_format(&s).as_ref(), "Maße", "{:?}", c);
+        }
+
+        // only those, and only at the ends
+        assert_eq!(UniCase::new_trim_format(" Foo\u{FEFF}").as_ref(), " Foo");
+        assert_eq!(UniCase::new_trim_format("\u{AD}Foo").as_ref(), "\u{AD}Foo");
+        assert_eq!(
+            UniCase::new_trim_format("\u{2061}Foo").as_ref(),
+            "\u{2061}Foo"
+        );
+        assert_eq!(
+            UniCase::new_trim_format("F\u{200B}oo").as_ref(),
+            "F\u{200B}oo"
+        );
+        assert_eq!(UniCase::new_trim_format("\u{FEFF}\u{200B}").as_ref(), "");
+    }
+
+    #[test]
+    fn test_new_bounded() {
+        let at = UniCase::new_bounded("Host", 4).unwrap();
+        assert_eq!(at, UniCase::new("HOST"));
+        assert!(at.is_ascii());
+        assert!(UniCase::new_bounded("Hos", 4).is_some());
+        assert!(UniCase::new_bounded("Hosts", 4).is_none());
+        assert!(UniCase::new_bounded("", 0).is_some());
+
+        // the limit is in bytes of the original string
+        let s = String::from("Maße");
+        assert_eq!(s.len(), 5);
+        assert!(!UniCase::new_bounded(s.clone(), 5).unwrap().is_ascii());
+        assert!(UniCase::new_bounded(s, 4).is_none());
+    }
+
+    #[test]
+    fn test_truncate_redetects_mode() {
+        let mut a = UniCase::new(String::from("a\u{e9}"));
+        assert!(!a.is_ascii());
+        a.truncate(1);
+        assert_eq!(a.as_ref(), "a");
+        assert!(a.is_ascii());
+        assert_eq!(a, UniCase::new("A"));
+
+        let mut b = UniCase::new(String::from("\u{e9}ab"));
+        b.truncate(3);
+        assert!(!b.is_ascii());
+        b.truncate(10);
+        assert_eq!(b.as_ref(), "\u{e9}a");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_truncate_not_char_boundary() {
+        UniCase::new(String::from("a\u{e9}")).truncate(2);
+    }
+
+    #[test]
+    fn test_insert_redetects_mode() {
+        let mut a = UniCase::new(String::from("Keln"));
+        assert!(a.is_ascii());
+        a.insert(1, '\u{212A}');
+        assert!(!a.is_ascii());
+        // Unicode folding: the Kelvin sign and `ß` fold like `k` and `ss`
+        assert_eq!(a, UniCase::new("kkeln"));
+        a.insert_str(a.len(), "ß");
+        assert_eq!(a.as_ref(), "K\u{212A}elnß");
+        assert_eq!(a, UniCase::new("KKELNSS"));
+
+        // ASCII text keeps ASCII mode
+        let mut b = UniCase::new(String::from("ab"));
+        b.insert_str(1, "XY");
+        b.insert(4, 'Z');
+        assert!(b.is_ascii());
+        assert_eq!(b, UniCase::new("axybz"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_not_char_boundary() {
+        UniCase::new(String::from("\u{e9}")).insert_str(1, "a");
+    }
+
+    #[test]
+    fn test_into_boxed_str() {
+        let a = UniCase::new(String::from("Maße"));
+        assert_eq!(&*a.clone().into_boxed_str(), "Maße");
+        assert_eq!(&*a.into_folded_boxed_str(), "masse");
+
+        let b = UniCase::new("Content-Type");
+        assert_eq!(&*b.into_boxed_str(), "Content-Type");
+        assert_eq!(&*b.into_folded_boxed_str(), "content-type");
+
+        // non-ASCII text gets the full fold, even in ASCII mode
+        assert_eq!(&*UniCase::ascii("ÄB").into_folded_boxed_str(), "äb");
+    }
+
+    #[test]
+    fn test_eq_char() {
+        assert!(UniCase::new("K") == 'k');
+        assert!(UniCase::new("k") == 'K');
+        assert!('k' == UniCase::new("K"));
+        assert!(UniCase::new("K") != 'x');
+
+        // the Kelvin sign folds to `k`
+        assert!(UniCase::new("\u{212A}") == 'k');
+        assert!(UniCase::new("K") == '\u{212A}');
+        // forced ASCII mode on non-ASCII text folds fully, as `==` does
+        assert!(UniCase::ascii("\u{212A}") == 'k');
+
+        assert!(UniCase::new("ß") == 'ß');
+        assert!(UniCase::new("ß") == 'ẞ');
+        assert!(UniCase::new("ss") != 'ß');
+
+        assert!(UniCase::new("") != 'a');
+        assert!(UniCase::new("ab") != 'a');
+    }
+
+    #[test]
+    fn test_index() {
+        let a = UniCase::new("Hello World");
+        assert_eq!(&a[0..5], "Hello");
+        assert_eq!(&a[6..], "World");
+        assert_eq!(&a[..5], "Hello");
+        assert_eq!(&a[..], "Hello World");
+        assert_eq!(&a[0..=4], "Hello");
+        assert_eq!(&a[..=4], "Hello");
+
+        let b = UniCase::new(String::from("Straße"));
+        let at = b.find("ß").unwrap();
+        assert_eq!(&b[at..], "ße");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_not_char_boundary() {
+        let _ = &UniCase::new("Maße")[..3];
+    }
+
+    #[test]
+    fn test_slice_keeps_mode() {
+        let a = UniCase::new("Maße Straße");
+        let b = a.slice(0..2);
+        assert_eq!(b.as_ref(), "Ma");
+        assert!(!a.is_ascii());
+        assert!(!b.is_ascii());
+        assert_eq!(b, UniCase::new("MA"));
+
+        let c = UniCase::ascii("ÄBC");
+        let d = c.slice(2..4);
+        assert!(d.is_ascii());
+        assert_eq!(d.as_ref(), "BC");
+
+        // the slice borrows from the original string, not from `a`
+        let tail = {
+            let a = UniCase::new("xx-Tail");
+            a.slice(3..7)
+        };
+        assert_eq!(tail, UniCase::new("tail"));
+    }
+
+    #[test]
+    fn test_split_at() {
+        let whole = UniCase::new("Content-Straße");
+        let (a, b) = whole.split_at(8);
+        assert_eq!(a.as_ref(), "Content-");
+        assert_eq!(b.as_ref(), "Straße");
+        assert!(a.is_ascii());
+        assert!(!b.is_ascii());
+        assert_eq!(a, UniCase::new("CONTENT-"));
+        assert_eq!(b, UniCase::new("STRASSE"));
+
+        let (a, b) = whole.split_at(0);
+        assert_eq!(a.as_ref(), "");
+        assert_eq!(b, whole);
+        let (a, b) = whole.split_at(whole.len());
+        assert_eq!(a, whole);
+        assert!(b.is_empty());
+    }
+
+    #[test]
+    fn test_trim() {
+        let a = UniCase::new("  Foo  ");
+        assert_eq!(a.trim(), UniCase::new("foo"));
+        assert_eq!(a.trim().as_ref(), "Foo");
+        assert_eq!(a.trim_start().as_ref(), "Foo  ");
+        assert_eq!(a.trim_end().as_ref(), "  Foo");
+        assert!(a.trim().is_ascii());
+
+        // non-ASCII whitespace around ASCII text
+        let b = UniCase::new("\u{3000}Host\u{A0}");
+        assert!(!b.is_ascii());
+        assert!(b.trim().is_ascii());
+        assert_eq!(b.trim(), UniCase::new("HOST"));
+        assert!(!b.trim_start().is_ascii());
+
+        assert_eq!(UniCase::new(" Maße ").trim(), UniCase::new("MASSE"));
+        assert!(UniCase::new(" \t\n").trim().is_empty());
+    }
+
+    #[test]
+    fn test_lines_ci() {
+        let text = String::from("GET /index\r\nHost: Example\nX-Name: Straße\n\n");
+        let lines: Vec<UniCase<&str>> = UniCase::new(&*text).lines_ci().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], UniCase::new("get /INDEX"));
+        assert!(lines[1].eq_ci("host: example"));
+        assert!(lines[0].is_ascii() && lines[1].is_ascii());
+        assert!(!lines[2].is_ascii());
+        assert_eq!(lines[2], UniCase::new("x-name: STRASSE"));
+        assert!(lines[3].is_empty());
+
+        // a forced mode on the whole string is not inherited
+        let mut lines = UniCase::unicode("A\nB").lines_ci();
+        assert!(lines.next().unwrap().is_ascii());
+        assert_eq!(UniCase::new("").lines_ci().count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_split_at_not_char_boundary() {
+        UniCase::new("Maße").split_at(3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_slice_not_char_boundary() {
+        UniCase::new("Maße").slice(0..3);
+    }
+
+    #[test]
+    fn test_eq_ignore_ws_ci() {
+        let a = UniCase::new("Foo Bar");
+        assert!(a.eq_ignore_ws_ci("foobar"));
+        assert!(a.eq_ignore_ws_ci("  FOO  BAR\n"));
+        assert!(a.eq_ignore_ws_ci("f o o b a r"));
+        assert!(a.eq_ignore_ws_ci("foo\u{3000}bar"));
+        assert!(!a.eq_ignore_ws_ci("foobarx"));
+        assert!(!a.eq_ignore_ws_ci("foo-bar"));
+
+        assert!(UniCase::new(" Stra ße").eq_ignore_ws_ci("STRASSE "));
+        assert!(UniCase::new(" \t").eq_ignore_ws_ci(""));
+        assert!(!UniCase::new("").eq_ignore_ws_ci("x"));
+    }
+
+    #[test]
+    fn test_to_folded() {
+        for &s in &["content-type", "", "masse", "σίσυφοσ", "ä-b"] {
+            match UniCase::new(s).to_folded() {
+                Cow::Borrowed(b) => assert_eq!(b, s),
+                Cow::Owned(o) => panic!("{:?} was folded to {:?}", s, o),
+            }
+        }
+        for &s in &["Content-Type", "Maße", "ΣΊΣΥΦΟΣ", "σίσυφος", "aß", "ﬁle"] {
+            let a = UniCase::new(s);
+            match a.to_folded() {
+                Cow::Owned(o) => assert_eq!(o, a.to_folded_case()),
+                Cow::Borrowed(b) => panic!("{:?} was borrowed", b),
+            }
+        }
+
+        // non-ASCII text gets the full fold, even in ASCII mode
+        assert!(matches!(
+            UniCase::ascii("äb").to_folded(),
+            Cow::Borrowed("äb")
+        ));
+        assert_eq!(UniCase::ascii("Äb").to_folded(), "äb");
+        assert_eq!(UniCase::ascii("äB").to_folded(), "äb");
+    }
+
+    #[test]
+    fn test_modify_redetects_mode() {
+        let mut a = UniCase::new(String::from("Astrom"));
+        assert!(a.is_ascii());
+        a.modify(|s| *s = String::from("Åström"));
+        assert!(!a.is_ascii());
+        assert_eq!(a, UniCase::ascii("åström"));
+        assert_eq!(a.to_folded_case(), "åström");
+        assert_eq!(hash_of(&a), hash_of(&UniCase::new("ÅSTRÖM")));
+
+        // what this avoids: `DerefMut` keeps the stale mode, which still
+        // folds correctly, but reports the value as ASCII
+        let mut b = UniCase::new(String::from("Astrom"));
+        *b = String::from("Åström");
+        assert!(b.is_ascii());
+        assert_eq!(b.to_folded_case(), "åström");
+
+        // and back to ASCII
+        a.modify(|s| *s = String::from("FOO"));
+        assert!(a.is_ascii());
+        assert_eq!(a, UniCase::new("foo"));
+        a.modify(|s| s.push('!'));
+        assert!(a.is_ascii());
+    }
+
+    #[test]
+    fn test_eq_chars() {
+        let a = UniCase::new("foo");
+        assert!(a.eq_chars("FOO".chars()));
+        assert!(a.eq_chars(['f', 'O', 'o'].iter().cloned()));
+        assert!(!a.eq_chars("FO".chars()));
+        assert!(!a.eq_chars("FOOO".chars()));
+        assert!(!a.eq_chars("FOX".chars()));
+
+        let b = UniCase::new("Maße");
+        assert!(b.eq_chars("MASSE".chars()));
+        // `ß` folds to two chars, which must both match
+        assert!(!b.eq_chars("MAS".chars()));
+        assert!(!b.eq_chars("MASSEE".chars()));
+        assert!(UniCase::new("MASSE").eq_chars("maße".chars()));
+
+        assert!(UniCase::new("").eq_chars("".chars()));
+        assert!(!UniCase::new("").eq_chars("a".chars()));
+
+        // only reads as far as the first difference
+        let mut chars = "FxOOOO".chars();
+        assert!(!a.eq_chars(chars.by_ref()));
+        assert_eq!(chars.as_str(), "OOOO");
+    }
+
+    #[test]
+    fn test_as_str() {
+        assert_eq!(UniCase::new("Maße").as_str(), "Maße");
+        assert_eq!(UniCase::new(String::from("Foo")).as_str(), "Foo");
+        assert_eq!(Ascii::new("Foo").as_str(), "Foo");
+        assert_eq!(UniCaseNoOpt::from_ref("Maße").as_str(), "Maße");
+        assert_eq!(UniCaseNoOpt::new(String::from("Foo")).as_str(), "Foo");
+    }
+
+    #[test]
+    fn test_len() {
+        let a = UniCase::new("Maße");
+        assert_eq!(a.len(), 5);
+        assert_eq!(a.char_count(), 4);
+        assert!(!a.is_empty());
+
+        let b = UniCase::new(String::new());
+        assert_eq!(b.len(), 0);
+        assert_eq!(b.char_count(), 0);
+        assert!(b.is_empty());
+
+        // the other wrappers share the same methods
+        let c = Ascii::new("Maße");
+        assert_eq!((c.len(), c.char_count(), c.is_empty()), (5, 4, false));
+        let d = UniCaseNoOpt::from_ref("Maße");
+        assert_eq!((d.len(), d.char_count(), d.is_empty()), (5, 4, false));
+        assert!(UniCaseNoOpt::new("").is_empty());
+    }
+
+    #[test]
+    fn test_to_uppercase_string() {
+        let a = UniCase::new("straße");
+        assert_eq!(a.to_uppercase_string(), "STRASSE");
+        assert_eq!(a.to_ascii_uppercase_string(), "STRAßE");
+        assert_eq!(a.to_folded_case(), "strasse");
+
+        let b = UniCase::new("Foo-Bar");
+        assert_eq!(b.to_uppercase_string(), "FOO-BAR");
+        assert_eq!(b.to_ascii_uppercase_string(), "FOO-BAR");
+
+        // ASCII mode never touches non-ASCII chars
+        assert_eq!(UniCase::ascii("straße").to_uppercase_string(), "STRAßE");
+    }
+
     #[test]
     fn test_unicase_unicode_const() {
         const _UNICASE: UniCase<&'static str> = UniCase::unicode("");
@@ -0,0 +1,251 @@
+#[cfg(any(__unicase__const_literal, feature = "std"))]
+use super::UniCase;
+
+/// Returns whether a string matches any of several literals, ignoring case.
+///
+/// The value can be anything that is `AsRef<str>`, such as a `&str`, a
+/// `String`, or a (reference to a) `UniCase`. It is evaluated once, and each
+/// alternative is compared with [`unicase::eq`](crate::eq), so nothing is
+/// allocated.
+///
+/// # Example
+///
+/// ```rust
+/// use unicase::{ci_matches, UniCase};
+///
+/// let method = UniCase::new(String::from("Post"));
+/// assert!(ci_matches!(&method, "get" | "post" | "put"));
+/// assert!(!ci_matches!("DELETE", "get" | "post" | "put"));
+/// ```
+#[macro_export]
+macro_rules! ci_matches {
+    ($value:expr, $($lit:literal)|+ $(,)?) => {
+        // `match` keeps any temporaries in `$value` alive
+        match &$value {
+            value => {
+                let value: &str = $crate::__as_str(value);
+                false $(|| $crate::eq(value, $lit))+
+            }
+        }
+    };
+}
+
+/// Asserts that two strings are equal, ignoring case.
+///
+/// Both sides can be anything that is `AsRef<str>`, and are compared like
+/// [`unicase::eq`](crate::eq). On failure, the panic message shows each
+/// side as written and as folded, so it is clear where they differ. Like
+/// `assert_eq!`, a custom message can follow the two values.
+///
+/// Only available with the `std` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use unicase::assert_ci_eq;
+///
+/// assert_ci_eq!("Straße", "STRASSE");
+/// assert_ci_eq!(String::from("Content-Type"), "content-type", "header {}", 1);
+/// ```
+///
+/// A mismatch panics with:
+///
+/// ```text
+/// assertion `left == right` failed (ignoring case)
+///   left: "Maße" (folded: "masse")
+///  right: "MAST" (folded: "mast")
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! assert_ci_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_ci_eq!($left, $right, "")
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                let left: &str = $crate::__as_str(left);
+                let right: &str = $crate::__as_str(right);
+                if !$crate::eq(left, right) {
+                    $crate::__assert_ci_failed(left, right, format_args!($($arg)+));
+                }
+            }
+        }
+    };
+}
+
+/// Used by `assert_ci_eq!`, so the formatting isn't expanded into every
+/// call site.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[cold]
+#[track_caller]
+pub fn __assert_ci_failed(left: &str, right: &str, msg: core::fmt::Arguments) -> ! {
+    let message = std::format!("{}", msg);
+    let colon = if message.is_empty() { "" } else { ": " };
+    panic!(
+        "assertion `left == right` failed (ignoring case){}{}\n  left: {:?} (folded: {:?})\n right: {:?} (folded: {:?})",
+        colon,
+        message,
+        left,
+        UniCase::new(left).to_folded_case(),
+        right,
+        UniCase::new(right).to_folded_case(),
+    )
+}
+
+/// Creates a `UniCase<&'static str>` from a string literal, at compile time.
+///
+/// The folding mode is picked the same way as [`UniCase::new`](crate::UniCase::new):
+/// ASCII if the literal is all ASCII, Unicode otherwise. The check runs
+/// during compilation, so this can be used to initialize `static`s and
+/// `const`s, and costs nothing at runtime.
+///
+/// Requires Rust 1.46 or newer.
+///
+/// # Example
+///
+/// ```rust
+/// use unicase::{unicase, UniCase};
+///
+/// static CONTENT_TYPE: UniCase<&str> = unicase!("Content-Type");
+///
+/// assert_eq!(CONTENT_TYPE, UniCase::new("content-type"));
+/// assert!(CONTENT_TYPE.is_ascii());
+/// assert!(!unicase!("Maße").is_ascii());
+/// ```
+#[cfg(__unicase__const_literal)]
+#[macro_export]
+macro_rules! unicase {
+    ($lit:literal) => {{
+        const VALUE: $crate::UniCase<&'static str> = $crate::__literal($lit);
+        VALUE
+    }};
+}
+
+/// Used by `unicase!`.
+#[cfg(__unicase__const_literal)]
+#[clippy::msrv = "1.46"]
+#[doc(hidden)]
+pub const fn __literal(s: &'static str) -> UniCase<&'static str> {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] >= 0x80 {
+            return UniCase::unicode(s);
+        }
+        i += 1;
+    }
+    UniCase::ascii(s)
+}
+
+/// Used by `ci_matches!`, which can't name `core` in 2015 edition crates.
+#[doc(hidden)]
+#[inline]
+pub fn __as_str<S: AsRef<str> + ?Sized>(s: &S) -> &str {
+    s.as_ref()
+}
+
+/// Defines `as_str`, `len`, `is_empty` and `char_count` in an `impl` block
+/// of a wrapper that is `AsRef<str>`, for its original string.
+macro_rules! str_methods {
+    () => {
+        /// Returns the original string, as written.
+        #[inline]
+        pub fn as_str(&self) -> &str {
+            AsRef::<str>::as_ref(self)
+        }
+
+        /// Returns the length of the original string, in bytes.
+        #[inline]
+        pub fn len(&self) -> usize {
+            AsRef::<str>::as_ref(self).len()
+        }
+
+        /// Returns `true` if the string is empty.
+        #[inline]
+        pub fn is_empty(&self) -> bool {
+            AsRef::<str>::as_ref(self).is_empty()
+        }
+
+        /// Returns the number of chars in the original string.
+        ///
+        /// This counts the chars as written, not as folded: `"ß"` is one char,
+        /// even though it folds to `"ss"`.
+        #[inline]
+        pub fn char_count(&self) -> usize {
+            AsRef::<str>::as_ref(self).chars().count()
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::UniCase;
+    use std::string::String;
+
+    #[test]
+    fn test_ci_matches() {
+        assert!(ci_matches!("GET", "get" | "post"));
+        assert!(ci_matches!("Post", "get" | "post"));
+        assert!(!ci_matches!("PUT", "get" | "post"));
+        assert!(ci_matches!("Maße", "masse"));
+        assert!(!ci_matches!("", "get"));
+    }
+
+    #[cfg(__unicase__const_literal)]
+    #[test]
+    fn test_unicase_literal() {
+        static HOST: UniCase<&str> = unicase!("Host");
+        const STRASSE: UniCase<&str> = unicase!("Straße");
+
+        assert_eq!(HOST, UniCase::new("host"));
+        assert!(HOST.is_ascii());
+        assert_eq!(STRASSE, UniCase::new("STRASSE"));
+        assert!(!STRASSE.is_ascii());
+
+        let empty = unicase!("");
+        assert_eq!(empty, UniCase::new(""));
+        assert!(empty.is_ascii());
+        for &s in &["Content-Type", "Maße", "ΣΊΣΥΦΟΣ"] {
+            assert_eq!(crate::__literal(s).is_ascii(), UniCase::new(s).is_ascii());
+        }
+    }
+
+    #[test]
+    fn test_ci_matches_wrappers() {
+        let owned = UniCase::new(String::from("Content-Type"));
+        assert!(ci_matches!(owned, "content-length" | "content-type"));
+        assert!(ci_matches!(&owned, "CONTENT-TYPE"));
+        assert!(!ci_matches!(&owned, "accept" | "host",));
+
+        let s = String::from("HOST");
+        assert!(ci_matches!(s, "host"));
+        assert!(ci_matches!(&s, "accept" | "host"));
+        assert!(ci_matches!(UniCase::new("ETag"), "etag"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_assert_ci_eq() {
+        assert_ci_eq!("Maße", "MASSE");
+        assert_ci_eq!(UniCase::new("Host"), String::from("HOST"),);
+        assert_ci_eq!(&String::from("a"), "A", "with {}", "message");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic(expected = "assertion `left == right` failed (ignoring case)\n  \
+                               left: \"Maße\" (folded: \"masse\")\n \
+                               right: \"MAST\" (folded: \"mast\")")]
+    fn test_assert_ci_eq_message() {
+        assert_ci_eq!("Maße", "MAST");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic(expected = "failed (ignoring case): header 2 differs\n  left: \"ETag\"")]
+    fn test_assert_ci_eq_custom_message() {
+        assert_ci_eq!("ETag", "E-Tag", "header {} differs", 2);
+    }
+}
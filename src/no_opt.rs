@@ -0,0 +1,457 @@
+use alloc::boxed::Box;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+use super::hash::fold_hash;
+use super::unicode::Unicode;
+use super::UniCase;
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+/// A borrowed case-insensitive string, always compared with Unicode folding.
+///
+/// This is the borrowed form of [`UniCase`], for looking up `UniCase` keys
+/// in maps and caches with a plain `&str`. Every `UniCase<S>` borrows as a
+/// `UniCaseNoOpt<str>`, so any collection whose lookups go through `Borrow`
+/// (`HashMap`, `HashSet`, `lru::LruCache`, ...) accepts one as a query.
+///
+/// Unlike `UniCase`, it cannot store which mode it was made with, so it
+/// checks both strings for ASCII on every comparison, and only takes the
+/// ASCII fast path if both are. `UniCase` does the same check, so hashing,
+/// equality and ordering agree between the two for every string.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use unicase::{UniCase, UniCaseNoOpt};
+///
+/// let mut map = HashMap::new();
+/// map.insert(UniCase::new(String::from("Content-Type")), "text/html");
+///
+/// let key = UniCaseNoOpt::from_ref("content-type");
+/// assert_eq!(map.get(key), Some(&"text/html"));
+/// ```
+// `from_ref`, `from_boxed_str` and `from_arc` cast pointers to `str` into
+// pointers to `UniCaseNoOpt<str>`, which is only sound because of
+// `repr(transparent)`: it must not be removed, nor any field added.
+#[repr(transparent)]
+pub struct UniCaseNoOpt<S: ?Sized>(S);
+
+// what `repr(transparent)` guarantees, checked where it can be
+#[cfg(__unicase__const_fold_hash)]
+const _: () = {
+    use core::mem::{align_of, size_of};
+    assert!(size_of::<UniCaseNoOpt<[u8; 3]>>() == size_of::<[u8; 3]>());
+    assert!(align_of::<UniCaseNoOpt<u64>>() == align_of::<u64>());
+    assert!(size_of::<&UniCaseNoOpt<str>>() == size_of::<&str>());
+    assert!(size_of::<Box<UniCaseNoOpt<str>>>() == size_of::<Box<str>>());
+};
+
+impl<S> UniCaseNoOpt<S> {
+    /// Wraps a string, to always be compared with Unicode folding.
+    ///
+    /// Nothing is scanned, so this is as cheap as [`Ascii::new`](crate::Ascii::new).
+    #[inline]
+    pub const fn new(s: S) -> UniCaseNoOpt<S> {
+        UniCaseNoOpt(s)
+    }
+
+    /// Unwraps the inner value.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+impl UniCaseNoOpt<str> {
+    /// Wraps a `&str` without copying it.
+    #[inline]
+    pub fn from_ref(s: &str) -> &UniCaseNoOpt<str> {
+        // SAFETY: `UniCaseNoOpt` is `repr(transparent)` over `str`, so the
+        // two have the same layout and pointer metadata.
+        unsafe { &*(s as *const str as *const UniCaseNoOpt<str>) }
+    }
+
+    /// Wraps a `Box<str>` without copying it.
+    ///
+    /// This gives a `Box<UniCaseNoOpt<str>>` rather than a `Box<UniCase<str>>`:
+    /// a `UniCase` stores its folding mode next to the string, so it can't
+    /// wrap an unsized `str` in place. The two compare and hash the same, and
+    /// a `UniCaseNoOpt<str>` looks up `UniCase` keys.
+    #[inline]
+    pub fn from_boxed_str(s: Box<str>) -> Box<UniCaseNoOpt<str>> {
+        // SAFETY: as in `from_ref`; the allocation is handed over unchanged,
+        // and freeing it as a `UniCaseNoOpt<str>` uses the same layout.
+        unsafe { Box::from_raw(Box::into_raw(s) as *mut UniCaseNoOpt<str>) }
+    }
+
+    /// Wraps an `Arc<str>` without copying it.
+    ///
+    /// An `Arc<UniCaseNoOpt<str>>` hashes and compares like the
+    /// `UniCaseNoOpt<str>` inside, so it works as a shared, cheaply cloned
+    /// case-insensitive key: one allocation can be the key in several maps,
+    /// or be handed between threads, and lookups still take a plain `&str`
+    /// through [`from_ref`](UniCaseNoOpt::from_ref).
+    ///
+    /// To make one from a `&str`, go through `Arc<str>` with `Arc::from`, or
+    /// convert a borrowed `&UniCaseNoOpt<str>` directly.
+    ///
+    /// There is no `Arc<UniCase<str>>`, as `UniCase` stores its folding mode
+    /// next to the string and can't wrap an unsized `str`. This is the
+    /// shared key to use instead: it compares and hashes like `UniCase`, and
+    /// `UniCase` keys borrow as it.
+    ///
+    /// Only available with the `std` feature.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use std::sync::Arc;
+    /// use unicase::UniCaseNoOpt;
+    ///
+    /// let key = UniCaseNoOpt::from_arc(Arc::from("Content-Type"));
+    /// let same: Arc<UniCaseNoOpt<str>> = Arc::from(UniCaseNoOpt::from_ref("CONTENT-TYPE"));
+    /// assert_eq!(key, same);
+    ///
+    /// let mut sizes = HashMap::new();
+    /// let mut types = HashMap::new();
+    /// sizes.insert(Arc::clone(&key), 12);
+    /// types.insert(key, "text/html");
+    ///
+    /// let query = UniCaseNoOpt::from_ref("content-type");
+    /// assert_eq!(sizes.get(query), Some(&12));
+    /// assert_eq!(types.get(query), Some(&"text/html"));
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn from_arc(s: Arc<str>) -> Arc<UniCaseNoOpt<str>> {
+        // SAFETY: as in `from_boxed_str`, for the `Arc`'s allocation.
+        unsafe { Arc::from_raw(Arc::into_raw(s) as *const UniCaseNoOpt<str>) }
+    }
+}
+
+impl From<&UniCaseNoOpt<str>> for Box<UniCaseNoOpt<str>> {
+    #[inline]
+    fn from(s: &UniCaseNoOpt<str>) -> Box<UniCaseNoOpt<str>> {
+        UniCaseNoOpt::from_boxed_str(Box::from(s.as_ref()))
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<&UniCaseNoOpt<str>> for Arc<UniCaseNoOpt<str>> {
+    #[inline]
+    fn from(s: &UniCaseNoOpt<str>) -> Arc<UniCaseNoOpt<str>> {
+        UniCaseNoOpt::from_arc(Arc::from(s.as_ref()))
+    }
+}
+
+impl<S: ?Sized + AsRef<str>> UniCaseNoOpt<S> {
+    str_methods!();
+}
+
+impl<S: ?Sized + AsRef<str>> AsRef<str> for UniCaseNoOpt<S> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+impl<S: ?Sized + fmt::Debug> fmt::Debug for UniCaseNoOpt<S> {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, fmt)
+    }
+}
+
+impl<S1, S2> PartialEq<UniCaseNoOpt<S2>> for UniCaseNoOpt<S1>
+where
+    S1: ?Sized + AsRef<str>,
+    S2: ?Sized + AsRef<str>,
+{
+    #[inline]
+    fn eq(&self, other: &UniCaseNoOpt<S2>) -> bool {
+        let (a, b) = (self.as_ref(), other.as_ref());
+        // both folds agree on ASCII text, and checking for it is cheaper
+        // than folding char by char
+        if a.is_ascii() && b.is_ascii() {
+            a.eq_ignore_ascii_case(b)
+        } else {
+            Unicode(a) == Unicode(b)
+        }
+    }
+}
+
+impl<S: ?Sized + AsRef<str>> Eq for UniCaseNoOpt<S> {}
+
+impl<S: ?Sized + AsRef<str>> Hash for UniCaseNoOpt<S> {
+    #[inline]
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        fold_hash(self.as_ref(), hasher)
+    }
+}
+
+impl<S: ?Sized + AsRef<str>> PartialOrd for UniCaseNoOpt<S> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Orders by the full Unicode case fold, exactly like `UniCase`'s `Ord`, so
+/// a `UniCaseNoOpt<str>` can look up and bound ranges of `UniCase` keys in
+/// a `BTreeMap` or `BTreeSet`.
+///
+/// `str` is unsized, so `a..b` of references doesn't make a range; use a
+/// pair of `Bound`s instead:
+///
+/// ```rust
+/// use std::collections::BTreeMap;
+/// use std::ops::Bound;
+/// use unicase::{UniCase, UniCaseNoOpt};
+///
+/// let mut map = BTreeMap::new();
+/// for &name in &["Apple", "banana", "Cherry", "date"] {
+///     map.insert(UniCase::new(name.to_owned()), ());
+/// }
+///
+/// let from = UniCaseNoOpt::from_ref("B");
+/// let to = UniCaseNoOpt::from_ref("d");
+/// let names: Vec<&str> = map
+///     .range::<UniCaseNoOpt<str>, _>((Bound::Included(from), Bound::Excluded(to)))
+///     .map(|(k, _)| k.as_ref())
+///     .collect();
+/// assert_eq!(names, ["banana", "Cherry"]);
+/// ```
+impl<S: ?Sized + AsRef<str>> Ord for UniCaseNoOpt<S> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        Unicode(self.as_ref()).cmp(&Unicode(other.as_ref()))
+    }
+}
+
+/// Compares as if the borrowed string were wrapped with `UniCase::new`.
+impl<S, T> PartialEq<UniCaseNoOpt<T>> for UniCase<S>
+where
+    S: AsRef<str>,
+    T: ?Sized + AsRef<str>,
+{
+    #[inline]
+    fn eq(&self, other: &UniCaseNoOpt<T>) -> bool {
+        self.eq_ci(other.as_ref())
+    }
+}
+
+impl<'a, S, T> PartialEq<&'a UniCaseNoOpt<T>> for UniCase<S>
+where
+    S: AsRef<str>,
+    T: ?Sized + AsRef<str>,
+{
+    #[inline]
+    fn eq(&self, other: &&'a UniCaseNoOpt<T>) -> bool {
+        self.eq_ci(other.as_ref())
+    }
+}
+
+impl<S, T> PartialEq<UniCase<S>> for UniCaseNoOpt<T>
+where
+    S: AsRef<str>,
+    T: ?Sized + AsRef<str>,
+{
+    #[inline]
+    fn eq(&self, other: &UniCase<S>) -> bool {
+        other == self
+    }
+}
+
+impl<S, T> PartialEq<UniCase<S>> for &UniCaseNoOpt<T>
+where
+    S: AsRef<str>,
+    T: ?Sized + AsRef<str>,
+{
+    #[inline]
+    fn eq(&self, other: &UniCase<S>) -> bool {
+        other == *self
+    }
+}
+
+impl<S: AsRef<str>> Borrow<UniCaseNoOpt<str>> for UniCase<S> {
+    #[inline]
+    fn borrow(&self) -> &UniCaseNoOpt<str> {
+        UniCaseNoOpt::from_ref(self.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UniCaseNoOpt;
+    use crate::hash::hash_of;
+    use crate::UniCase;
+    use std::boxed::Box;
+    use std::num::NonZeroUsize;
+    use std::string::String;
+
+    #[test]
+    fn test_new_into_inner() {
+        const KEY: UniCaseNoOpt<&str> = UniCaseNoOpt::new("Maße");
+        assert_eq!(KEY, *UniCaseNoOpt::from_ref("MASSE"));
+        assert_eq!(KEY.as_ref(), "Maße");
+        assert_eq!(KEY.into_inner(), "Maße");
+
+        let owned = UniCaseNoOpt::new(String::from("Foo"));
+        assert_eq!(owned, UniCase::new("FOO"));
+        assert_eq!(owned.into_inner(), "Foo");
+    }
+
+    #[test]
+    fn test_boxed() {
+        let s: Box<str> = Box::from("Maße");
+        let ptr = s.as_ptr();
+        let a = UniCaseNoOpt::from_boxed_str(s);
+        assert_eq!((*a).as_ref().as_ptr(), ptr);
+        let b: Box<UniCaseNoOpt<str>> = Box::from(UniCaseNoOpt::from_ref("MASSE"));
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_arc_keys() {
+        use std::collections::HashMap;
+        use std::sync::Arc;
+
+        let s: Arc<str> = Arc::from("Content-Type");
+        let ptr = s.as_ptr();
+        let a = UniCaseNoOpt::from_arc(s);
+        assert_eq!((*a).as_ref().as_ptr(), ptr);
+        let b: Arc<UniCaseNoOpt<str>> = Arc::from(UniCaseNoOpt::from_ref("CONTENT-TYPE"));
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+        assert_eq!(hash_of(&a), hash_of(&UniCase::new("content-type")));
+
+        let mut map = HashMap::new();
+        map.insert(Arc::clone(&a), 1);
+        assert_eq!(map.insert(b, 2), Some(1));
+        assert_eq!(map.get(UniCaseNoOpt::from_ref("content-TYPE")), Some(&2));
+        // the first key is kept, and shared with `a`
+        assert_eq!(Arc::strong_count(&a), 2);
+    }
+
+    /// Goes through every pointer cast, freeing through the cast pointers
+    /// too, so that Miri can check them: CI runs this module under
+    /// `cargo miri test`.
+    #[test]
+    fn test_casts() {
+        let s = String::from("Maße");
+        let r = UniCaseNoOpt::from_ref(&s);
+        assert_eq!(r.as_ref(), "Maße");
+
+        let boxed = UniCaseNoOpt::from_boxed_str(Box::from("MASSE"));
+        assert_eq!(&*boxed, r);
+        drop(boxed);
+
+        #[cfg(feature = "std")]
+        {
+            use std::sync::Arc;
+
+            let a = UniCaseNoOpt::from_arc(Arc::from("masse"));
+            let b = Arc::clone(&a);
+            drop(a);
+            assert_eq!(&*b, r);
+            drop(b);
+        }
+    }
+
+    #[test]
+    fn test_matches_unicase() {
+        for &(a, b) in &[("foobar", "FOOBAR"), ("Maße", "MASSE"), ("σ", "ς")] {
+            let a = UniCase::new(a);
+            let b = UniCaseNoOpt::from_ref(b);
+            assert_eq!(UniCaseNoOpt::from_ref(a.as_ref()), b);
+            assert_eq!(hash_of(&a), hash_of(b));
+        }
+        assert!(UniCaseNoOpt::from_ref("foo") != UniCaseNoOpt::from_ref("foobar"));
+    }
+
+    #[test]
+    fn test_eq_unicase() {
+        let owned = UniCase::new(String::from("foo"));
+        assert_eq!(owned, *UniCaseNoOpt::from_ref("Foo"));
+        assert_eq!(owned, UniCaseNoOpt::from_ref("FOO"));
+        assert_eq!(*UniCaseNoOpt::from_ref("Foo"), owned);
+        assert_eq!(UniCaseNoOpt::from_ref("fOO"), owned);
+        assert!(owned != UniCaseNoOpt::from_ref("foobar"));
+
+        let owned = UniCase::new(String::from("Maße"));
+        assert_eq!(owned, UniCaseNoOpt::from_ref("MASSE"));
+        assert_eq!(UniCaseNoOpt::from_ref("masse"), owned);
+    }
+
+    #[test]
+    fn test_ord_matches_unicase() {
+        let words = ["a", "B", "Maße", "MASSE", "mast", "ä", "Z", "", "ß", "SS"];
+        for &a in &words {
+            for &b in &words {
+                assert_eq!(
+                    UniCaseNoOpt::from_ref(a).cmp(UniCaseNoOpt::from_ref(b)),
+                    UniCase::new(a).cmp(&UniCase::new(b)),
+                    "{:?} vs {:?}",
+                    a,
+                    b
+                );
+            }
+        }
+        // including values forced into ASCII mode
+        assert_eq!(
+            UniCase::ascii("Ä").cmp(&UniCase::new("b")),
+            UniCaseNoOpt::from_ref("Ä").cmp(UniCaseNoOpt::from_ref("b"))
+        );
+    }
+
+    #[test]
+    fn test_btree_range() {
+        use std::collections::BTreeMap;
+        use std::ops::Bound;
+        use std::vec::Vec;
+
+        let mut map = BTreeMap::new();
+        for (i, &k) in ["apple", "Banana", "cherry", "Maße", "mast", "Zebra"]
+            .iter()
+            .enumerate()
+        {
+            map.insert(UniCase::new(String::from(k)), i);
+        }
+        let key = UniCaseNoOpt::from_ref;
+
+        let range = |lo: Bound<&'static str>, hi: Bound<&'static str>| -> Vec<usize> {
+            map.range::<UniCaseNoOpt<str>, _>((lo.map(key), hi.map(key)))
+                .map(|(_, &v)| v)
+                .collect()
+        };
+
+        assert_eq!(range(Bound::Included("B"), Bound::Excluded("M")), [1, 2]);
+        assert_eq!(
+            range(Bound::Included("MASSE"), Bound::Included("MAST")),
+            [3, 4]
+        );
+        assert_eq!(range(Bound::Excluded("masse"), Bound::Unbounded), [4, 5]);
+        assert_eq!(range(Bound::Unbounded, Bound::Excluded("BANANA")), [0]);
+
+        assert_eq!(map.get(key("CHERRY")), Some(&2));
+        assert_eq!(map.get(key("strasse")), None);
+    }
+
+    #[test]
+    fn test_lru_lookup() {
+        let mut cache = lru::LruCache::new(NonZeroUsize::new(2).unwrap());
+        cache.put(UniCase::new(String::from("Content-Type")), 1);
+        cache.put(UniCase::new(String::from("Straße")), 2);
+
+        assert_eq!(cache.get(UniCaseNoOpt::from_ref("content-type")), Some(&1));
+        assert_eq!(cache.peek(UniCaseNoOpt::from_ref("STRASSE")), Some(&2));
+        assert_eq!(cache.get(UniCaseNoOpt::from_ref("accept")), None);
+    }
+}
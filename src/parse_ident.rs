@@ -0,0 +1,121 @@
+use core::fmt;
+
+use super::UniCase;
+
+impl<'a> UniCase<&'a str> {
+    /// Checks that `s` is a plain ASCII identifier, and wraps it.
+    ///
+    /// The accepted grammar is `[A-Za-z_][A-Za-z0-9_]*`: an ASCII letter or
+    /// underscore, followed by any number of ASCII letters, digits and
+    /// underscores. So `"Foo_1"` and `"_"` are accepted, while `""`,
+    /// `"1bad"`, `"has space"`, `"kebab-case"` and `"Maße"` are not. An
+    /// accepted string is all ASCII, so it always gets ASCII folding.
+    ///
+    /// This is unrelated to the `Identifier` wrapper of the `identifier`
+    /// feature, which compares any Unicode identifier and accepts every
+    /// string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`IdentError`] pointing at the first char that doesn't
+    /// fit, or saying that `s` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let key = UniCase::parse_ident("Max_Size").unwrap();
+    /// assert_eq!(key, UniCase::new("MAX_SIZE"));
+    ///
+    /// let err = UniCase::parse_ident("max size").unwrap_err();
+    /// assert_eq!(err.to_string(), "invalid character ' ' at byte 3 of identifier");
+    /// ```
+    pub fn parse_ident(s: &'a str) -> Result<UniCase<&'a str>, IdentError> {
+        let mut chars = s.char_indices();
+        match chars.next() {
+            None => return Err(IdentError { found: None }),
+            Some((_, c)) if c.is_ascii_alphabetic() || c == '_' => (),
+            Some((i, c)) => {
+                return Err(IdentError {
+                    found: Some((i, c)),
+                })
+            }
+        }
+        for (i, c) in chars {
+            if !(c.is_ascii_alphanumeric() || c == '_') {
+                return Err(IdentError {
+                    found: Some((i, c)),
+                });
+            }
+        }
+        Ok(UniCase::ascii(s))
+    }
+}
+
+/// The error returned by [`UniCase::parse_ident`] when a string isn't an
+/// identifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IdentError {
+    found: Option<(usize, char)>,
+}
+
+impl IdentError {
+    /// Returns the byte position and value of the first char that isn't
+    /// allowed where it is, or `None` if the string was empty.
+    #[inline]
+    pub fn invalid_char(&self) -> Option<(usize, char)> {
+        self.found
+    }
+}
+
+impl fmt::Display for IdentError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.found {
+            None => fmt.write_str("empty identifier"),
+            Some((i, c)) => write!(fmt, "invalid character {:?} at byte {} of identifier", c, i),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IdentError {}
+
+#[cfg(test)]
+mod tests {
+    use super::IdentError;
+    use crate::UniCase;
+
+    #[test]
+    fn test_parse_ident() {
+        for &s in &["Foo_1", "_", "a", "_private", "SCREAMING_CASE", "x86_64"] {
+            let key = UniCase::parse_ident(s).unwrap();
+            assert_eq!(key.as_ref(), s);
+            assert!(key.is_ascii());
+        }
+        assert_eq!(
+            UniCase::parse_ident("Foo_1").unwrap(),
+            UniCase::new("FOO_1")
+        );
+
+        let err = |s| UniCase::parse_ident(s).unwrap_err().invalid_char();
+        assert_eq!(err(""), None);
+        assert_eq!(err("1bad"), Some((0, '1')));
+        assert_eq!(err("has space"), Some((3, ' ')));
+        assert_eq!(err("kebab-case"), Some((5, '-')));
+        assert_eq!(err("Maße"), Some((2, 'ß')));
+        assert_eq!(err("é"), Some((0, 'é')));
+    }
+
+    #[test]
+    fn test_ident_error_display() {
+        use std::string::ToString;
+
+        let empty: IdentError = UniCase::parse_ident("").unwrap_err();
+        assert_eq!(empty.to_string(), "empty identifier");
+        assert_eq!(
+            UniCase::parse_ident("1bad").unwrap_err().to_string(),
+            "invalid character '1' at byte 0 of identifier"
+        );
+    }
+}
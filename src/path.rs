@@ -0,0 +1,153 @@
+use std::ffi::{CStr, OsStr};
+use std::path::Path;
+
+use super::{Ascii, UniCase};
+
+macro_rules! os_str_impls {
+    ($ty:ty, $to_str:expr) => {
+        /// Compares against a path, OS or C string, ignoring case.
+        ///
+        /// This folds the same way as comparing two `UniCase`s. A value
+        /// that is not valid Unicode never compares equal, since a `str`
+        /// can't hold it.
+        impl<S: AsRef<str>> PartialEq<$ty> for UniCase<S> {
+            #[inline]
+            fn eq(&self, other: &$ty) -> bool {
+                match $to_str(other) {
+                    Some(s) => self.eq_ci(s),
+                    None => false,
+                }
+            }
+        }
+
+        impl<'a, S: AsRef<str>> PartialEq<&'a $ty> for UniCase<S> {
+            #[inline]
+            fn eq(&self, other: &&'a $ty) -> bool {
+                *self == **other
+            }
+        }
+
+        impl<S: AsRef<str>> PartialEq<UniCase<S>> for $ty {
+            #[inline]
+            fn eq(&self, other: &UniCase<S>) -> bool {
+                *other == *self
+            }
+        }
+    };
+}
+
+os_str_impls!(Path, Path::to_str);
+os_str_impls!(OsStr, OsStr::to_str);
+os_str_impls!(CStr, cstr_to_str);
+
+fn cstr_to_str(s: &CStr) -> Option<&str> {
+    s.to_str().ok()
+}
+
+impl<S: AsRef<str>> Ascii<S> {
+    /// Compares against a C string, ignoring ASCII case.
+    ///
+    /// The bytes of `c` up to its NUL terminator are compared with the
+    /// UTF-8 bytes of the string, like `[u8]::eq_ignore_ascii_case`: ASCII
+    /// letters match either case, and every other byte must be the same. So
+    /// no encoding is assumed for `c`, and nothing is allocated, but
+    /// non-ASCII text only matches if `c` holds it as UTF-8.
+    ///
+    /// There is no `PartialEq<CStr>` for `Ascii`, since it would overlap
+    /// with the `AsRef<str>` impl, so only `cstr == ascii` works as an
+    /// operator.
+    ///
+    /// Only available with the `std` feature.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::ffi::CStr;
+    /// use unicase::Ascii;
+    ///
+    /// let c = CStr::from_bytes_with_nul(b"content-type\0").unwrap();
+    /// assert!(Ascii::new("Content-Type").eq_cstr(c));
+    /// assert!(c == Ascii::new("CONTENT-TYPE"));
+    /// ```
+    #[inline]
+    pub fn eq_cstr(&self, c: &CStr) -> bool {
+        self.as_ref().as_bytes().eq_ignore_ascii_case(c.to_bytes())
+    }
+}
+
+impl<S: AsRef<str>> PartialEq<Ascii<S>> for CStr {
+    #[inline]
+    fn eq(&self, other: &Ascii<S>) -> bool {
+        other.eq_cstr(self)
+    }
+}
+
+impl<S: AsRef<str>> PartialEq<Ascii<S>> for &CStr {
+    #[inline]
+    fn eq(&self, other: &Ascii<S>) -> bool {
+        other.eq_cstr(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Ascii, UniCase};
+    use std::ffi::{CStr, OsStr};
+    use std::path::Path;
+    use std::string::String;
+
+    #[test]
+    fn test_eq_path() {
+        assert_eq!(UniCase::new("README.MD"), Path::new("readme.md"));
+        assert_eq!(
+            UniCase::new(String::from("src/Lib.rs")),
+            Path::new("SRC/lib.RS")
+        );
+        assert_eq!(*Path::new("Straße.txt"), UniCase::new("STRASSE.TXT"));
+        assert!(UniCase::new("README.MD") != Path::new("README"));
+    }
+
+    #[test]
+    fn test_eq_os_str() {
+        assert_eq!(UniCase::new("PATH"), OsStr::new("path"));
+        assert_eq!(*OsStr::new("Maße"), UniCase::new("MASSE"));
+        assert!(UniCase::new("PATH") != OsStr::new("HOME"));
+    }
+
+    #[test]
+    fn test_eq_cstr() {
+        let c = CStr::from_bytes_with_nul(b"content-type\0").unwrap();
+        assert!(Ascii::new("Content-Type").eq_cstr(c));
+        assert!(c == Ascii::new("CONTENT-TYPE"));
+        assert!(*c == Ascii::new(String::from("Content-Type")));
+        assert!(!Ascii::new("Content").eq_cstr(c));
+        assert!(!Ascii::new("Content-Type\0").eq_cstr(c));
+
+        // bytes after the first NUL are not part of the string
+        let c = CStr::from_bytes_until_nul(b"HOST\0junk\0").unwrap();
+        assert!(Ascii::new("host").eq_cstr(c));
+
+        // non-ASCII bytes are compared as they are
+        let c = CStr::from_bytes_with_nul(b"stra\xC3\x9Fe\0").unwrap();
+        assert!(Ascii::new("STRAßE").eq_cstr(c));
+        assert!(!Ascii::new("STRASSE").eq_cstr(c));
+        let c = CStr::from_bytes_with_nul(b"stra\xDFe\0").unwrap();
+        assert!(!Ascii::new("straße").eq_cstr(c));
+
+        assert_eq!(
+            UniCase::new("STRASSE"),
+            CStr::from_bytes_with_nul(b"stra\xC3\x9Fe\0").unwrap()
+        );
+        assert!(UniCase::new("straße") != c);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_eq_non_unicode() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let bad = OsStr::from_bytes(b"readme\xFF");
+        assert!(UniCase::new("README\u{FFFD}") != bad);
+        assert!(UniCase::new("README") != Path::new(bad));
+    }
+}
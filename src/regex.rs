@@ -0,0 +1,72 @@
+use ::regex::Regex;
+
+use super::UniCase;
+
+impl<S: AsRef<str>> UniCase<S> {
+    /// Returns whether `re` matches anywhere in the original string.
+    ///
+    /// This is a shortcut for `re.is_match(self.as_ref())`. The regex sees
+    /// the string as written, not folded, so for case-insensitive matching
+    /// give the pattern the `(?i)` flag.
+    ///
+    /// Note that `(?i)` and `UniCase` don't ignore case in quite the same
+    /// way. The regex engine uses simple case folding, one char to one
+    /// char, while `UniCase` uses full folding, where a char can fold to
+    /// several: `UniCase::new("ß") == UniCase::new("SS")`, but `(?i)ß` does
+    /// not match `"SS"`. Compare with `==` when that matters, and use a
+    /// regex when you need a pattern.
+    ///
+    /// Only available with the `regex` feature.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use regex::Regex;
+    /// use unicase::UniCase;
+    ///
+    /// let re = Regex::new(r"(?i)^content-").unwrap();
+    /// assert!(UniCase::new("Content-Type").is_match(&re));
+    ///
+    /// let re = Regex::new("(?i)^strasse$").unwrap();
+    /// assert!(!UniCase::new("Straße").is_match(&re));
+    /// assert_eq!(UniCase::new("Straße"), UniCase::new("strasse"));
+    /// ```
+    #[inline]
+    pub fn is_match(&self, re: &Regex) -> bool {
+        re.is_match(self.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::UniCase;
+    use regex::Regex;
+
+    #[test]
+    fn test_is_match() {
+        let re = Regex::new("^x-[a-z]+$").unwrap();
+        assert!(UniCase::new("x-custom").is_match(&re));
+        // the original string is matched, without folding
+        assert!(!UniCase::new("X-Custom").is_match(&re));
+
+        let re = Regex::new("(?i)^x-[a-z]+$").unwrap();
+        assert!(UniCase::new("X-Custom").is_match(&re));
+    }
+
+    #[test]
+    fn test_regex_simple_folding_differs() {
+        let sharp_s = Regex::new("(?i)^ß$").unwrap();
+        // simple folding: `ß` and capital `ẞ` are one char each
+        assert!(UniCase::new("ẞ").is_match(&sharp_s));
+        assert_eq!(UniCase::new("ß"), UniCase::new("ẞ"));
+
+        // full folding: `ß` is also `"ss"`, which only `UniCase` sees
+        assert!(!UniCase::new("SS").is_match(&sharp_s));
+        assert!(!UniCase::new("ss").is_match(&sharp_s));
+        assert_eq!(UniCase::new("ß"), UniCase::new("SS"));
+
+        let strasse = Regex::new("(?i)^strasse$").unwrap();
+        assert!(!UniCase::new("Straße").is_match(&strasse));
+        assert_eq!(UniCase::new("Straße"), UniCase::new("STRASSE"));
+    }
+}
@@ -0,0 +1,667 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::mem;
+
+use super::unicode::{lookup, Fold};
+use super::{ascii_fast_path, FoldedChars, UniCase};
+
+/// Returns how many bytes at the start of `a` case-insensitively match the
+/// start of `b`, using Unicode case folding.
+///
+/// The returned length is measured in the *original* bytes of `a`, so it can
+/// be used directly to slice `a`.
+///
+/// A character only counts once its full fold has matched, on both sides.
+/// Folds that expand to several characters are never split part way: `"ß"`
+/// shares a prefix of length 2 with `"SS"`, but none with `"Sx"`.
+///
+/// # Example
+///
+/// ```rust
+/// let len = unicase::common_prefix_ci("FooBar", "foobaz");
+/// assert_eq!(len, 5);
+/// assert_eq!(&"FooBar"[..len], "FooBa");
+/// ```
+pub fn common_prefix_ci(a: &str, b: &str) -> usize {
+    let mut right = b.chars().map(lookup);
+    let mut pending = Fold::Zero;
+    let mut matched = 0;
+
+    for (i, c) in a.char_indices() {
+        for x in lookup(c) {
+            if pending.size_hint().0 == 0 {
+                pending = match right.next() {
+                    Some(fold) => fold,
+                    None => return matched,
+                };
+            }
+            if pending.next() != Some(x) {
+                return matched;
+            }
+        }
+        // only a boundary if the current char of `b` was fully consumed too
+        if pending.size_hint().0 == 0 {
+            matched = i + c.len_utf8();
+        }
+    }
+    matched
+}
+
+/// Returns the Levenshtein edit distance between `a` and `b`, ignoring case.
+///
+/// The distance is computed over the Unicode case-folded chars of both
+/// strings, so differences in case cost nothing, and an edit is counted per
+/// folded char: `"ß"` is one edit away from `"s"`, since it folds to `"ss"`.
+///
+/// # Example
+///
+/// ```rust
+/// assert_eq!(unicase::levenshtein_ci("Finalizes", "finalises"), 1);
+/// assert_eq!(unicase::levenshtein_ci("HELLO", "hello"), 0);
+/// ```
+pub fn levenshtein_ci(a: &str, b: &str) -> usize {
+    let mut long: Vec<char> = a.chars().flat_map(lookup).collect();
+    let mut short: Vec<char> = b.chars().flat_map(lookup).collect();
+    if short.len() > long.len() {
+        mem::swap(&mut long, &mut short);
+    }
+
+    // a single row of the table, indexed by position in `short`
+    let mut row: Vec<usize> = (0..=short.len()).collect();
+    for (i, &x) in long.iter().enumerate() {
+        let mut diag = row[0];
+        row[0] = i + 1;
+        for (j, &y) in short.iter().enumerate() {
+            let cost = if x == y { diag } else { diag + 1 };
+            diag = row[j + 1];
+            row[j + 1] = cost.min(row[j] + 1).min(diag + 1);
+        }
+    }
+    row[short.len()]
+}
+
+/// Splits `s` into words and yields each one case-folded.
+///
+/// Words are separated by whitespace as defined by `char::is_whitespace`,
+/// like `str::split_whitespace`. Each word is folded the way
+/// [`UniCase::to_folded_case`] would fold it, so ASCII words take the fast
+/// path.
+///
+/// # Example
+///
+/// ```rust
+/// let words: Vec<String> = unicase::fold_words("Hello WORLD \u{FB01}le").collect();
+/// assert_eq!(words, ["hello", "world", "file"]);
+/// ```
+pub fn fold_words(s: &str) -> impl Iterator<Item = String> + '_ {
+    s.split_whitespace()
+        .map(|word| UniCase::new(word).to_folded_case())
+}
+
+impl<S: AsRef<str>> UniCase<S> {
+    /// Returns the index of the first folded character at which `self` and
+    /// `other` differ, or `None` if they are case-insensitively equal.
+    ///
+    /// The index counts characters of the *folded* strings, so it may not
+    /// line up with character positions in the originals when a fold
+    /// changes length (`ß` folds to `ss`).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// assert_eq!(UniCase::new("FooBar").first_difference("foobar"), None);
+    /// assert_eq!(UniCase::new("FooBar").first_difference("foobaz"), Some(5));
+    /// ```
+    pub fn first_difference(&self, other: &str) -> Option<usize> {
+        let (mut left, mut right) = self.fold_pair(other);
+        let mut i = 0;
+        loop {
+            match (left.next(), right.next()) {
+                (None, None) => return None,
+                (x, y) if x == y => i += 1,
+                _ => return Some(i),
+            }
+        }
+    }
+
+    /// Returns `true` if the first `n` folded chars of `self` and `other`
+    /// are equal, like a case-insensitive `strncmp`.
+    ///
+    /// `n` counts chars of the *folded* strings, as
+    /// [`first_difference`](UniCase::first_difference) does, so `"Maße"` and
+    /// `"MASK"` agree on 3 chars but not on 4. If either string folds to
+    /// fewer than `n` chars, both must fold to the same, shorter, string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let a = UniCase::new("Content-Type");
+    /// assert!(a.eq_prefix_ci("CONTENT-LENGTH", 8));
+    /// assert!(!a.eq_prefix_ci("CONTENT-LENGTH", 9));
+    /// ```
+    pub fn eq_prefix_ci(&self, other: &str, n: usize) -> bool {
+        let (left, right) = self.fold_pair(other);
+        left.take(n).eq(right.take(n))
+    }
+
+    /// Returns the index of the first of `prefixes` that the string starts
+    /// with, ignoring case, or `None` if it starts with none of them.
+    ///
+    /// Prefixes are tried in order, so when several match, as `"con"` and
+    /// `"content-"` both do for `"Content-Type"`, the earlier one wins: put
+    /// longer prefixes first to prefer them. Each has to match whole chars
+    /// of the string, with the same folding `==` would use, like
+    /// [`split_ci`](UniCase::split_ci): an empty prefix always matches, and
+    /// `"Maße"` starts with `"MASS"`, but not with `"MAS"`, which would split
+    /// the fold of `ß`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let path = UniCase::new("/API/v1/users");
+    /// assert_eq!(path.starts_with_any(&["/static/", "/api/"]), Some(1));
+    /// assert_eq!(path.starts_with_any(&["/admin/"]), None);
+    /// assert_eq!(UniCase::new("Maße").starts_with_any(&["MAS", "MASS"]), Some(1));
+    /// ```
+    pub fn starts_with_any(&self, prefixes: &[&str]) -> Option<usize> {
+        let s = self.as_ref();
+        prefixes.iter().position(|prefix| {
+            let ascii = ascii_fast_path(s, self.is_ascii(), prefix);
+            folded_prefix_len(s, prefix, ascii).is_some()
+        })
+    }
+
+    /// Returns the index of the first of `suffixes` that the string ends
+    /// with, ignoring case, or `None` if it ends with none of them.
+    ///
+    /// Like [`starts_with_any`](UniCase::starts_with_any), the earliest
+    /// match wins, and a suffix has to match whole chars of the string, so
+    /// `"Straße"` ends with `"SSE"` but not with `"SE"`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let file = UniCase::new("Photo.JPEG");
+    /// assert_eq!(file.ends_with_any(&[".png", ".jpg", ".jpeg"]), Some(2));
+    /// ```
+    pub fn ends_with_any(&self, suffixes: &[&str]) -> Option<usize> {
+        let s = self.as_ref();
+        suffixes.iter().position(|suffix| {
+            let ascii = ascii_fast_path(s, self.is_ascii(), suffix);
+            folded_suffix_start(s, suffix, ascii).is_some()
+        })
+    }
+
+    /// Returns `true` if the string contains `c`, ignoring case.
+    ///
+    /// This looks for whole chars of the string that fold to the same as
+    /// `c`, like [`split_ci`](UniCase::split_ci) does for its separator. A
+    /// fold is never split, so `"STRASSE"` contains `'ß'`, but `"Maße"`
+    /// doesn't contain `'s'`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// assert!(UniCase::new("Hello").contains_char_ci('L'));
+    /// assert!(!UniCase::new("Hello").contains_char_ci('x'));
+    /// ```
+    pub fn contains_char_ci(&self, c: char) -> bool {
+        let mut buf = [0; 4];
+        let c = &*c.encode_utf8(&mut buf);
+        let ascii = ascii_fast_path(self.as_ref(), self.is_ascii(), c);
+        find_folded(self.as_ref(), c, ascii).is_some()
+    }
+
+    /// Returns `true` if `word` appears in the string as a whole word,
+    /// ignoring case.
+    ///
+    /// A match only counts if it is not directly preceded or followed by an
+    /// alphanumeric character, as defined by `char::is_alphanumeric`. Every
+    /// other character, including `_`, separates words. An empty `word`
+    /// never matches.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// assert!(UniCase::new("The Cat sat").contains_word_ci("cat"));
+    /// assert!(!UniCase::new("concatenate").contains_word_ci("cat"));
+    /// ```
+    pub fn contains_word_ci(&self, word: &str) -> bool {
+        if word.is_empty() {
+            return false;
+        }
+        let s = self.as_ref();
+        let ascii = ascii_fast_path(s, self.is_ascii(), word);
+        let mut prev_alphanumeric = false;
+        for (i, c) in s.char_indices() {
+            if !prev_alphanumeric {
+                if let Some(len) = folded_prefix_len(&s[i..], word, ascii) {
+                    let at_boundary = match s[i + len..].chars().next() {
+                        Some(next) => !next.is_alphanumeric(),
+                        None => true,
+                    };
+                    if at_boundary {
+                        return true;
+                    }
+                }
+            }
+            prev_alphanumeric = c.is_alphanumeric();
+        }
+        false
+    }
+
+    /// Splits the string on case-insensitive occurrences of `sep`, yielding
+    /// slices of the original string.
+    ///
+    /// Like `str::split`, separators at either end or next to each other
+    /// produce empty slices. A separator only matches whole characters of
+    /// the string, so `"ß"` is never split by `"s"`. An empty `sep` yields
+    /// the whole string once.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let query = UniCase::new("foo OR bar or baz");
+    /// let parts: Vec<&str> = query.split_ci(" or ").collect();
+    /// assert_eq!(parts, ["foo", "bar", "baz"]);
+    /// ```
+    pub fn split_ci<'a>(&'a self, sep: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        SplitCi {
+            rest: Some(self.as_ref()),
+            sep,
+            ascii: ascii_fast_path(self.as_ref(), self.is_ascii(), sep),
+            terminator: false,
+        }
+    }
+
+    /// Like [`split_ci`](UniCase::split_ci), but if the last slice is empty,
+    /// it is skipped, like `str::split_terminator`.
+    ///
+    /// This is for strings where `sep` ends each item rather than coming
+    /// between them, so a trailing separator doesn't produce an empty item.
+    /// Only the very last slice is dropped: separators next to each other,
+    /// or at the start, still produce empty slices, and a string that ends
+    /// in two separators yields one empty slice at the end.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let s = UniCase::new("aSEPbSEP");
+    /// assert_eq!(s.split_terminator_ci("sep").collect::<Vec<_>>(), ["a", "b"]);
+    /// assert_eq!(s.split_ci("sep").collect::<Vec<_>>(), ["a", "b", ""]);
+    /// ```
+    pub fn split_terminator_ci<'a>(&'a self, sep: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        SplitCi {
+            rest: Some(self.as_ref()),
+            sep,
+            ascii: ascii_fast_path(self.as_ref(), self.is_ascii(), sep),
+            terminator: true,
+        }
+    }
+}
+
+struct SplitCi<'a> {
+    rest: Option<&'a str>,
+    sep: &'a str,
+    ascii: bool,
+    /// Skip the last slice if it is empty.
+    terminator: bool,
+}
+
+impl<'a> Iterator for SplitCi<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let rest = self.rest?;
+        if !self.sep.is_empty() {
+            if let Some((start, end)) = find_folded(rest, self.sep, self.ascii) {
+                self.rest = Some(&rest[end..]);
+                return Some(&rest[..start]);
+            }
+        }
+        self.rest = None;
+        if self.terminator && rest.is_empty() {
+            None
+        } else {
+            Some(rest)
+        }
+    }
+}
+
+/// Finds the first case-insensitive match of `needle` in `haystack`,
+/// returning its byte range.
+fn find_folded(haystack: &str, needle: &str, ascii: bool) -> Option<(usize, usize)> {
+    for (i, _) in haystack.char_indices() {
+        if let Some(len) = folded_prefix_len(&haystack[i..], needle, ascii) {
+            return Some((i, i + len));
+        }
+    }
+    None
+}
+
+/// If some prefix of `haystack` folds to exactly the folded `needle`,
+/// returns the length of that prefix in bytes.
+fn folded_prefix_len(haystack: &str, needle: &str, ascii: bool) -> Option<usize> {
+    let mut needle = FoldedChars::new(needle, ascii);
+    let mut buf = [0; 4];
+    let mut len = 0;
+    for c in haystack.chars() {
+        if needle.clone().next().is_none() {
+            return Some(len);
+        }
+        for x in FoldedChars::new(c.encode_utf8(&mut buf), ascii) {
+            if needle.next() != Some(x) {
+                return None;
+            }
+        }
+        len += c.len_utf8();
+    }
+    match needle.next() {
+        None => Some(len),
+        Some(_) => None,
+    }
+}
+
+/// If some suffix of `haystack` folds to exactly the folded `needle`,
+/// returns where that suffix starts.
+fn folded_suffix_start(haystack: &str, needle: &str, ascii: bool) -> Option<usize> {
+    // only one suffix folds to as many chars as `needle`, if any
+    let want = FoldedChars::new(needle, ascii).count();
+    let mut buf = [0; 4];
+    let mut have = 0;
+    let mut start = haystack.len();
+    for (i, c) in haystack.char_indices().rev() {
+        if have >= want {
+            break;
+        }
+        have += FoldedChars::new(c.encode_utf8(&mut buf), ascii).count();
+        start = i;
+    }
+    if have == want
+        && folded_prefix_len(&haystack[start..], needle, ascii) == Some(haystack.len() - start)
+    {
+        Some(start)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{common_prefix_ci, fold_words, levenshtein_ci};
+    use crate::UniCase;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_common_prefix_ci() {
+        assert_eq!(common_prefix_ci("FooBar", "foobaz"), 5);
+        assert_eq!(common_prefix_ci("foo", "FOOBAR"), 3);
+        assert_eq!(common_prefix_ci("FOOBAR", "foo"), 3);
+        assert_eq!(common_prefix_ci("", "foo"), 0);
+        assert_eq!(common_prefix_ci("foo", ""), 0);
+        assert_eq!(common_prefix_ci("bar", "foo"), 0);
+    }
+
+    #[test]
+    fn test_common_prefix_ci_unicode() {
+        assert_eq!(common_prefix_ci("Straße", "STRASSE"), "Straße".len());
+        assert_eq!(common_prefix_ci("STRASSE", "Straße"), "STRASSE".len());
+        assert_eq!(common_prefix_ci("Στιγμας", "στιγμασ"), "Στιγμας".len());
+    }
+
+    #[test]
+    fn test_common_prefix_ci_partial_fold() {
+        // `ß` folds to `ss`, which must match as a whole
+        assert_eq!(common_prefix_ci("ß", "sx"), 0);
+        assert_eq!(common_prefix_ci("aß", "as"), 1);
+        // and a single `s` doesn't consume all of `ß`
+        assert_eq!(common_prefix_ci("s", "ß"), 0);
+        assert_eq!(common_prefix_ci("ss", "ß"), 2);
+    }
+
+    #[test]
+    fn test_levenshtein_ci() {
+        assert_eq!(levenshtein_ci("Finalizes", "finalises"), 1);
+        assert_eq!(levenshtein_ci("KITTEN", "sitting"), 3);
+        assert_eq!(levenshtein_ci("sitting", "KITTEN"), 3);
+        assert_eq!(levenshtein_ci("FooBar", "fOObAR"), 0);
+        assert_eq!(levenshtein_ci("", "abc"), 3);
+        assert_eq!(levenshtein_ci("ABC", ""), 3);
+        assert_eq!(levenshtein_ci("", ""), 0);
+    }
+
+    #[test]
+    fn test_levenshtein_ci_unicode() {
+        assert_eq!(levenshtein_ci("Maße", "MASSE"), 0);
+        assert_eq!(levenshtein_ci("ß", "s"), 1);
+        assert_eq!(levenshtein_ci("ΣΊΣΥΦΟΣ", "σίσυφος"), 0);
+    }
+
+    #[test]
+    fn test_fold_words() {
+        let words: Vec<_> = fold_words("Hello WORLD \u{FB01}le").collect();
+        assert_eq!(words, ["hello", "world", "file"]);
+
+        let words: Vec<_> = fold_words("  Straße\tΣΊΣΥΦΟΣ\u{3000}x \n").collect();
+        assert_eq!(words, ["strasse", "σίσυφοσ", "x"]);
+
+        assert_eq!(fold_words(" \t ").count(), 0);
+    }
+
+    #[test]
+    fn test_first_difference() {
+        assert_eq!(UniCase::new("foobar").first_difference("FOOBAR"), None);
+        assert_eq!(UniCase::new("").first_difference(""), None);
+        assert_eq!(UniCase::new("foobar").first_difference("xoobar"), Some(0));
+        assert_eq!(UniCase::new("foo").first_difference("foobar"), Some(3));
+        assert_eq!(UniCase::new("foobar").first_difference("foo"), Some(3));
+    }
+
+    #[test]
+    fn test_first_difference_unicode() {
+        assert_eq!(UniCase::new("Maße").first_difference("MASSE"), None);
+        // `ß` becomes two folded chars, so the `x` is at folded index 4
+        assert_eq!(UniCase::new("Maße").first_difference("MASSX"), Some(4));
+        assert_eq!(UniCase::new("MASSX").first_difference("Maße"), Some(4));
+    }
+
+    #[test]
+    fn test_eq_prefix_ci() {
+        let a = UniCase::new("Content-Type");
+        assert!(a.eq_prefix_ci("content-length", 8));
+        assert!(!a.eq_prefix_ci("content-length", 9));
+        assert!(a.eq_prefix_ci("xyz", 0));
+        assert!(a.eq_prefix_ci("CONTENT-TYPE", 100));
+        assert!(!a.eq_prefix_ci("CONTENT", 100));
+        assert!(!UniCase::new("CONTENT").eq_prefix_ci("Content-Type", 8));
+        assert!(UniCase::new("").eq_prefix_ci("", 3));
+
+        // `n` counts folded chars, and `ß` folds to two
+        let b = UniCase::new("Maße");
+        assert!(b.eq_prefix_ci("MASK", 3));
+        assert!(!b.eq_prefix_ci("MASK", 4));
+        assert!(b.eq_prefix_ci("MASSX", 4));
+        assert!(UniCase::new("MASSX").eq_prefix_ci("maß", 4));
+    }
+
+    #[test]
+    fn test_starts_with_any() {
+        let a = UniCase::new("Content-Type");
+        // overlapping prefixes: the first one listed wins
+        assert_eq!(a.starts_with_any(&["con", "content-"]), Some(0));
+        assert_eq!(a.starts_with_any(&["content-", "con"]), Some(0));
+        assert_eq!(a.starts_with_any(&["accept", "CONTENT-", "c"]), Some(1));
+        assert_eq!(a.starts_with_any(&["x-", "Content-Typed"]), None);
+        assert_eq!(a.starts_with_any(&[]), None);
+        assert_eq!(a.starts_with_any(&["x", ""]), Some(1));
+
+        let b = UniCase::new("Maße");
+        assert_eq!(b.starts_with_any(&["MASSEN", "MASS"]), Some(1));
+        // a prefix may not split a fold
+        assert_eq!(b.starts_with_any(&["MAS"]), None);
+        assert_eq!(UniCase::new("ß").starts_with_any(&["s"]), None);
+        assert_eq!(UniCase::new("ß").starts_with_any(&["s", "SS"]), Some(1));
+        // like `==`, a non-ASCII prefix is compared with Unicode folding
+        assert_eq!(UniCase::ascii("Äb").starts_with_any(&["ä"]), Some(0));
+        assert_eq!(UniCase::ascii("Äb").starts_with_any(&["x", "ÄB"]), Some(1));
+    }
+
+    #[test]
+    fn test_ends_with_any() {
+        let a = UniCase::new("archive.TAR.GZ");
+        assert_eq!(a.ends_with_any(&[".gz", ".tar.gz"]), Some(0));
+        assert_eq!(a.ends_with_any(&[".zip", ".Tar.Gz", ".gz"]), Some(1));
+        assert_eq!(a.ends_with_any(&[".zip", "xarchive.tar.gz"]), None);
+
+        let b = UniCase::new("Straße");
+        assert_eq!(b.ends_with_any(&["SSE"]), Some(0));
+        assert_eq!(b.ends_with_any(&["e", "SE"]), Some(0));
+        assert_eq!(b.ends_with_any(&["STRASSE!", "ASSE"]), Some(1));
+        assert_eq!(b.ends_with_any(&["straße", ""]), Some(0));
+        // a suffix may not split a fold
+        assert_eq!(b.ends_with_any(&["SE"]), None);
+        assert_eq!(UniCase::new("ß").ends_with_any(&["s"]), None);
+        assert_eq!(
+            UniCase::new("aﬃ").ends_with_any(&["i", "fi", "FFI"]),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_contains_char_ci() {
+        assert!(UniCase::new("Hello").contains_char_ci('L'));
+        assert!(UniCase::new("Hello").contains_char_ci('h'));
+        assert!(UniCase::ascii("Hello").contains_char_ci('O'));
+        assert!(!UniCase::new("Hello").contains_char_ci('x'));
+        assert!(!UniCase::new("").contains_char_ci('x'));
+    }
+
+    #[test]
+    fn test_contains_char_ci_unicode() {
+        assert!(UniCase::new("Στιγμας").contains_char_ci('Σ'));
+        // the Kelvin sign folds to an ASCII `k`
+        assert!(UniCase::new("kelvin").contains_char_ci('\u{212A}'));
+        // whole chars of the string match, but never part of a fold
+        assert!(UniCase::new("Straße").contains_char_ci('s'));
+        assert!(!UniCase::new("Maße").contains_char_ci('s'));
+        assert!(UniCase::new("STRASSE").contains_char_ci('ß'));
+        assert!(!UniCase::new("STRASE").contains_char_ci('ß'));
+    }
+
+    #[test]
+    fn test_contains_word_ci() {
+        let s = UniCase::new("The Cat sat on the mat.");
+        assert!(s.contains_word_ci("cat"));
+        assert!(s.contains_word_ci("THE"));
+        assert!(s.contains_word_ci("mat"));
+        assert!(s.contains_word_ci("cat sat"));
+        assert!(!s.contains_word_ci("at"));
+        assert!(!s.contains_word_ci(""));
+
+        assert!(!UniCase::new("concatenate").contains_word_ci("cat"));
+        assert!(!UniCase::new("cats").contains_word_ci("cat"));
+        assert!(UniCase::new("cat_dog").contains_word_ci("cat"));
+        assert!(UniCase::new("CAT").contains_word_ci("cat"));
+    }
+
+    #[test]
+    fn test_contains_word_ci_unicode() {
+        assert!(UniCase::new("Die Straße ist lang").contains_word_ci("STRASSE"));
+        assert!(!UniCase::new("Hauptstraße").contains_word_ci("strasse"));
+        assert!(!UniCase::new("Straßen").contains_word_ci("strasse"));
+        // a fold may not be split to end a word
+        assert!(!UniCase::new("Maß").contains_word_ci("mas"));
+    }
+
+    #[test]
+    fn test_split_ci() {
+        let s = UniCase::new("aXbXc");
+        assert_eq!(s.split_ci("x").collect::<Vec<_>>(), ["a", "b", "c"]);
+        assert_eq!(s.split_ci("X").collect::<Vec<_>>(), ["a", "b", "c"]);
+        assert_eq!(s.split_ci("y").collect::<Vec<_>>(), ["aXbXc"]);
+        assert_eq!(s.split_ci("").collect::<Vec<_>>(), ["aXbXc"]);
+
+        let s = UniCase::new("XaxxbX");
+        assert_eq!(s.split_ci("x").collect::<Vec<_>>(), ["", "a", "", "b", ""]);
+
+        let s = UniCase::new("");
+        assert_eq!(s.split_ci("x").collect::<Vec<_>>(), [""]);
+    }
+
+    #[test]
+    fn test_split_terminator_ci() {
+        let s = UniCase::new("aSEPbSEP");
+        assert_eq!(s.split_terminator_ci("sep").collect::<Vec<_>>(), ["a", "b"]);
+        let s = UniCase::new("aSEPb");
+        assert_eq!(s.split_terminator_ci("sep").collect::<Vec<_>>(), ["a", "b"]);
+
+        // only the last empty slice is skipped
+        let s = UniCase::new("XaxxbXx");
+        assert_eq!(
+            s.split_terminator_ci("x").collect::<Vec<_>>(),
+            ["", "a", "", "b", ""]
+        );
+        assert_eq!(
+            UniCase::new("X")
+                .split_terminator_ci("x")
+                .collect::<Vec<_>>(),
+            [""]
+        );
+        assert_eq!(UniCase::new("").split_terminator_ci("x").count(), 0);
+        assert_eq!(
+            UniCase::new("ab")
+                .split_terminator_ci("")
+                .collect::<Vec<_>>(),
+            ["ab"]
+        );
+
+        // agrees with `str::split_terminator` on ASCII-lowercase input
+        for &s in &["", "a", ";", "a;", ";a", "a;;b;", ";;", "a;b;c"] {
+            assert_eq!(
+                UniCase::new(s).split_terminator_ci(";").collect::<Vec<_>>(),
+                s.split_terminator(';').collect::<Vec<_>>(),
+                "{:?}",
+                s
+            );
+        }
+
+        let s = UniCase::new("einsSTRASSEzweistraße");
+        assert_eq!(
+            s.split_terminator_ci("Straße").collect::<Vec<_>>(),
+            ["eins", "zwei"]
+        );
+    }
+
+    #[test]
+    fn test_split_ci_unicode() {
+        let s = UniCase::new("einsSTRASSEzweistraßedrei");
+        assert_eq!(
+            s.split_ci("Straße").collect::<Vec<_>>(),
+            ["eins", "zwei", "drei"]
+        );
+        // separators can't split a multi-char fold
+        let s = UniCase::new("aßb");
+        assert_eq!(s.split_ci("s").collect::<Vec<_>>(), ["aßb"]);
+
+        // forced ASCII mode on non-ASCII text still folds fully
+        let s = UniCase::ascii("a\u{212A}b");
+        assert_eq!(s.split_ci("k").collect::<Vec<_>>(), ["a", "b"]);
+        assert!(UniCase::ascii("1 \u{212A}").contains_word_ci("K"));
+    }
+}
@@ -0,0 +1,1104 @@
+//! Serde support, and helpers for use with `#[serde(with = "...")]`.
+//!
+//! `UniCase`, `Ascii` and `UniCaseNoOpt` serialize as their original
+//! string, and deserialize from any string. Only available with the `serde`
+//! feature.
+//!
+//! `UniCaseNoOpt` deserializes into a `UniCaseNoOpt<String>` or a
+//! `Box<UniCaseNoOpt<str>>`, the form its maps usually store as keys.
+//!
+//! Like `String`, the owned `UniCase<String>` and `Ascii<String>` also
+//! accept a `char`, or bytes that are valid UTF-8, and reject everything
+//! else. To accept numbers as well, use [`numbers`] on the field.
+//!
+//! With the `compact_str` feature, `UniCase<CompactString>` and
+//! `Ascii<CompactString>` deserialize like their `String` counterparts,
+//! without needing `compact_str`'s own `serde` feature.
+
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::{fmt, str};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use ::serde::de::{self, Deserialize, Deserializer, SeqAccess, Unexpected, Visitor};
+use ::serde::ser::{Serialize, Serializer};
+#[cfg(feature = "compact_str")]
+use compact_str::CompactString;
+
+use super::{Ascii, AsciiBytes, UniCase, UniCaseNoOpt};
+
+macro_rules! serialize_impl {
+    ($wrapper:ident $(, ?$sized:ident)?) => {
+        impl<S: $(?$sized +)? AsRef<str>> Serialize for $wrapper<S> {
+            fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+                serializer.serialize_str(self.as_ref())
+            }
+        }
+    };
+}
+
+macro_rules! deserialize_owned_impl {
+    ($wrapper:ident) => {
+        impl<'de> Deserialize<'de> for $wrapper<String> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                String::deserialize(deserializer).map($wrapper::new)
+            }
+        }
+    };
+}
+
+macro_rules! deserialize_borrowed_impl {
+    ($wrapper:ident) => {
+        impl<'de: 'a, 'a> Deserialize<'de> for $wrapper<&'a str> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                <&'a str>::deserialize(deserializer).map($wrapper::new)
+            }
+        }
+    };
+}
+
+macro_rules! deserialize_cow_impl {
+    ($wrapper:ident) => {
+        impl<'de: 'a, 'a> Deserialize<'de> for $wrapper<Cow<'a, str>> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer
+                    .deserialize_str(CowStrVisitor)
+                    .map($wrapper::new)
+            }
+        }
+    };
+}
+
+/// Borrows from the input when possible, unlike serde's own `Cow<str>`
+/// impl, which always allocates.
+///
+/// This relies on asking for `deserialize_str` rather than
+/// `deserialize_string`, which tells the deserializer that borrowed data is
+/// welcome. Containers still need `#[serde(borrow)]` on the field, since
+/// serde only infers it for plain `&str` and `Cow<str>`.
+struct CowStrVisitor;
+
+impl<'de> Visitor<'de> for CowStrVisitor {
+    type Value = Cow<'de, str>;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("a string")
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, s: &'de str) -> Result<Self::Value, E> {
+        Ok(Cow::Borrowed(s))
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+        Ok(Cow::Owned(String::from(s)))
+    }
+
+    fn visit_string<E: de::Error>(self, s: String) -> Result<Self::Value, E> {
+        Ok(Cow::Owned(s))
+    }
+
+    // some formats hand out strings as bytes, such as for identifiers; like
+    // `String`, accept them if they are UTF-8
+
+    fn visit_borrowed_bytes<E: de::Error>(self, b: &'de [u8]) -> Result<Self::Value, E> {
+        match str::from_utf8(b) {
+            Ok(s) => Ok(Cow::Borrowed(s)),
+            Err(_) => Err(E::invalid_value(Unexpected::Bytes(b), &self)),
+        }
+    }
+
+    fn visit_bytes<E: de::Error>(self, b: &[u8]) -> Result<Self::Value, E> {
+        match str::from_utf8(b) {
+            Ok(s) => Ok(Cow::Owned(String::from(s))),
+            Err(_) => Err(E::invalid_value(Unexpected::Bytes(b), &self)),
+        }
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, b: Vec<u8>) -> Result<Self::Value, E> {
+        match String::from_utf8(b) {
+            Ok(s) => Ok(Cow::Owned(s)),
+            Err(e) => Err(E::invalid_value(Unexpected::Bytes(&e.into_bytes()), &self)),
+        }
+    }
+}
+
+serialize_impl!(UniCase);
+serialize_impl!(Ascii);
+serialize_impl!(UniCaseNoOpt, ?Sized);
+
+deserialize_owned_impl!(UniCase);
+deserialize_owned_impl!(Ascii);
+deserialize_owned_impl!(UniCaseNoOpt);
+
+impl<'de> Deserialize<'de> for Box<UniCaseNoOpt<str>> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Box::<str>::deserialize(deserializer).map(UniCaseNoOpt::from_boxed_str)
+    }
+}
+
+deserialize_borrowed_impl!(UniCase);
+deserialize_borrowed_impl!(Ascii);
+
+deserialize_cow_impl!(UniCase);
+deserialize_cow_impl!(Ascii);
+
+#[cfg(feature = "compact_str")]
+macro_rules! deserialize_compact_impl {
+    ($wrapper:ident) => {
+        impl<'de> Deserialize<'de> for $wrapper<CompactString> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                // short strings are copied inline, without an intermediate
+                // `String`, and long owned ones keep their allocation
+                deserializer
+                    .deserialize_str(CowStrVisitor)
+                    .map(|s| $wrapper::new(CompactString::from(s)))
+            }
+        }
+    };
+}
+
+#[cfg(feature = "compact_str")]
+deserialize_compact_impl!(UniCase);
+#[cfg(feature = "compact_str")]
+deserialize_compact_impl!(Ascii);
+
+impl<B: AsRef<[u8]>> Serialize for AsciiBytes<B> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        serializer.serialize_bytes(self.as_ref())
+    }
+}
+
+impl<'de> Deserialize<'de> for AsciiBytes<Vec<u8>> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_byte_buf(ByteBufVisitor)
+            .map(AsciiBytes::new)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for AsciiBytes<&'a [u8]> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <&'a [u8]>::deserialize(deserializer).map(AsciiBytes::new)
+    }
+}
+
+/// Accepts bytes however the format hands them out. `Vec<u8>`'s own impl
+/// only takes a sequence, which formats with a native bytes type don't use.
+struct ByteBufVisitor;
+
+impl<'de> Visitor<'de> for ByteBufVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("a byte string")
+    }
+
+    fn visit_bytes<E: de::Error>(self, b: &[u8]) -> Result<Self::Value, E> {
+        Ok(Vec::from(b))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, b: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(b)
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+        Ok(Vec::from(s.as_bytes()))
+    }
+
+    fn visit_string<E: de::Error>(self, s: String) -> Result<Self::Value, E> {
+        Ok(s.into_bytes())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(b) = seq.next_element()? {
+            bytes.push(b);
+        }
+        Ok(bytes)
+    }
+}
+
+/// Serializes a `HashMap` with `UniCase` keys in sorted key order.
+///
+/// `HashMap` iterates in an arbitrary order, which differs from run to run.
+/// This sorts the entries the way `UniCase`'s `Ord` does, by their folded
+/// keys, so the output is the same every time, like a `BTreeMap`'s. Keys
+/// are still written with their original casing.
+///
+/// Use it with `#[serde(serialize_with = "unicase::serde::sorted_map")]`.
+/// It collects and sorts references to the entries, so it allocates one
+/// `Vec` as long as the map.
+///
+/// Only available with the `std` feature.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate serde_derive;
+/// use std::collections::HashMap;
+/// use serde_derive::Serialize;
+/// use unicase::UniCase;
+///
+/// #[derive(Serialize)]
+/// struct Headers {
+///     #[serde(serialize_with = "unicase::serde::sorted_map")]
+///     map: HashMap<UniCase<String>, String>,
+/// }
+///
+/// let mut map = HashMap::new();
+/// map.insert(UniCase::new("Host".to_owned()), "example.com".to_owned());
+/// map.insert(UniCase::new("accept".to_owned()), "*/*".to_owned());
+///
+/// let json = serde_json::to_string(&Headers { map }).unwrap();
+/// assert_eq!(json, r#"{"map":{"accept":"*/*","Host":"example.com"}}"#);
+/// ```
+#[cfg(feature = "std")]
+pub fn sorted_map<S, V, H, Ser>(
+    map: &HashMap<UniCase<S>, V, H>,
+    serializer: Ser,
+) -> Result<Ser::Ok, Ser::Error>
+where
+    S: AsRef<str>,
+    V: Serialize,
+    Ser: Serializer,
+{
+    let mut entries: Vec<(&UniCase<S>, &V)> = map.iter().collect();
+    // keys that differ only in folding mode can fold the same, so fall back
+    // to the original strings to keep the order total
+    entries.sort_by(|a, b| a.0.cmp(b.0).then_with(|| a.0.as_ref().cmp(b.0.as_ref())));
+    serializer.collect_map(entries)
+}
+
+/// Deserializes a `UniCase<String>` with surrounding whitespace trimmed.
+///
+/// For config files and other hand-written input, where keys can come with
+/// stray spaces as well as inconsistent casing. Use it with
+/// `#[serde(deserialize_with = "unicase::serde::trimmed::deserialize")]`.
+/// Whitespace is trimmed like `str::trim`, and the folding mode is picked
+/// from the trimmed string, as [`UniCase::new`] would.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate serde_derive;
+/// use serde_derive::Deserialize;
+/// use unicase::UniCase;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     #[serde(deserialize_with = "unicase::serde::trimmed::deserialize")]
+///     key: UniCase<String>,
+/// }
+///
+/// let config: Config = serde_json::from_str(r#"{ "key": "  Foo  " }"#).unwrap();
+/// assert_eq!(config.key, UniCase::new("foo"));
+/// assert_eq!(config.key.as_ref(), "Foo");
+/// ```
+pub mod trimmed {
+    use alloc::string::String;
+
+    use serde::de::{Deserialize, Deserializer};
+
+    use crate::UniCase;
+
+    /// Deserializes a string, trims it, and wraps it with `UniCase::new`.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<UniCase<String>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let trimmed = s.trim();
+        if trimmed.len() == s.len() {
+            Ok(UniCase::new(s))
+        } else {
+            Ok(UniCase::new(String::from(trimmed)))
+        }
+    }
+}
+
+/// Deserializes a `UniCase` or `Ascii` from a string or a number.
+///
+/// Some inputs write numeric-looking keys as numbers, such as `404` in a
+/// list of status codes. Use this module on a field with
+/// `#[serde(deserialize_with = "unicase::serde::numbers::deserialize")]`,
+/// or `deserialize_ascii` for an `Ascii`, to accept those too. Numbers are
+/// written out with their `Display` impl, so `42` becomes `"42"` and `1.5`
+/// becomes `"1.5"`, and booleans are still rejected.
+///
+/// This asks the format for `deserialize_any`, so it only works with
+/// self-describing formats, such as JSON. That's why it's opt-in per field,
+/// and the default `Deserialize` impls only ask for a string.
+///
+/// Only available with the `serde-numbers` feature.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate serde_derive;
+/// use serde_derive::Deserialize;
+/// use unicase::UniCase;
+///
+/// #[derive(Deserialize)]
+/// struct Route {
+///     #[serde(deserialize_with = "unicase::serde::numbers::deserialize")]
+///     status: UniCase<String>,
+/// }
+///
+/// let route: Route = serde_json::from_str(r#"{ "status": 404 }"#).unwrap();
+/// assert_eq!(route.status, UniCase::new("404"));
+/// ```
+#[cfg(feature = "serde-numbers")]
+pub mod numbers {
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+    use core::{fmt, str};
+
+    use serde::de::{self, Deserializer, Unexpected, Visitor};
+
+    use crate::{Ascii, UniCase};
+
+    /// Deserializes a string or a number, and wraps it with `UniCase::new`.
+    pub fn deserialize<'de, S, D>(deserializer: D) -> Result<UniCase<S>, D::Error>
+    where
+        S: From<String> + AsRef<str>,
+        D: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_any(StringOrNumberVisitor)
+            .map(|s| UniCase::new(S::from(s)))
+    }
+
+    /// Deserializes a string or a number, and wraps it with `Ascii::new`.
+    pub fn deserialize_ascii<'de, S, D>(deserializer: D) -> Result<Ascii<S>, D::Error>
+    where
+        S: From<String>,
+        D: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_any(StringOrNumberVisitor)
+            .map(|s| Ascii::new(S::from(s)))
+    }
+
+    /// Accepts what `String` does, plus numbers, which are written out with
+    /// their `Display` impl.
+    ///
+    /// Formats such as JSON only call `visit_u64` and friends when they get to
+    /// pick, hence `deserialize_any`.
+    struct StringOrNumberVisitor;
+
+    macro_rules! visit_number {
+        ($($method:ident: $ty:ty),*) => {
+            $(
+                fn $method<E: de::Error>(self, n: $ty) -> Result<Self::Value, E> {
+                    Ok(n.to_string())
+                }
+            )*
+        };
+    }
+
+    impl<'de> Visitor<'de> for StringOrNumberVisitor {
+        type Value = String;
+
+        fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            fmt.write_str("a string or a number")
+        }
+
+        fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+            Ok(String::from(s))
+        }
+
+        fn visit_string<E: de::Error>(self, s: String) -> Result<Self::Value, E> {
+            Ok(s)
+        }
+
+        fn visit_char<E: de::Error>(self, c: char) -> Result<Self::Value, E> {
+            Ok(c.to_string())
+        }
+
+        fn visit_bytes<E: de::Error>(self, b: &[u8]) -> Result<Self::Value, E> {
+            match str::from_utf8(b) {
+                Ok(s) => Ok(String::from(s)),
+                Err(_) => Err(E::invalid_value(Unexpected::Bytes(b), &self)),
+            }
+        }
+
+        fn visit_byte_buf<E: de::Error>(self, b: Vec<u8>) -> Result<Self::Value, E> {
+            match String::from_utf8(b) {
+                Ok(s) => Ok(s),
+                Err(e) => Err(E::invalid_value(Unexpected::Bytes(&e.into_bytes()), &self)),
+            }
+        }
+
+        visit_number!(
+            visit_i64: i64,
+            visit_i128: i128,
+            visit_u64: u64,
+            visit_u128: u128,
+            visit_f64: f64
+        );
+    }
+}
+
+/// Serializes a `UniCase` together with its folding mode.
+///
+/// The default representation is just the string, and deserializing picks
+/// the mode again like [`UniCase::new`], so a value forced into one mode
+/// with [`UniCase::ascii`] or [`UniCase::unicode`] comes back in the other
+/// if its contents say so. Use this module with
+/// `#[serde(with = "unicase::serde::with_mode")]` on a field to write it as
+/// a struct instead, `{ "mode": "ascii", "value": "..." }`, and restore the
+/// exact mode on load. The mode is `"ascii"` or `"unicode"`.
+///
+/// Only available with the `serde-mode` feature.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate serde_derive;
+/// use serde_derive::{Deserialize, Serialize};
+/// use unicase::UniCase;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Key {
+///     #[serde(with = "unicase::serde::with_mode")]
+///     name: UniCase<String>,
+/// }
+///
+/// let key = Key { name: UniCase::ascii("Ä".to_owned()) };
+/// let json = serde_json::to_string(&key).unwrap();
+/// assert_eq!(json, r#"{"name":{"mode":"ascii","value":"Ä"}}"#);
+///
+/// let key: Key = serde_json::from_str(&json).unwrap();
+/// assert!(key.name.is_ascii());
+/// ```
+#[cfg(feature = "serde-mode")]
+pub mod with_mode {
+    use core::fmt;
+    use core::marker::PhantomData;
+
+    use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+    use serde::ser::{SerializeStruct, Serializer};
+
+    use crate::UniCase;
+
+    const FIELDS: &[&str] = &["mode", "value"];
+    const MODES: &[&str] = &["ascii", "unicode"];
+
+    /// Serializes `value` as a struct of its mode and string.
+    pub fn serialize<S, Ser>(value: &UniCase<S>, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        S: AsRef<str>,
+        Ser: Serializer,
+    {
+        let mode = if value.is_ascii() { "ascii" } else { "unicode" };
+        let mut state = serializer.serialize_struct("UniCase", 2)?;
+        state.serialize_field("mode", mode)?;
+        state.serialize_field("value", value.as_ref())?;
+        state.end()
+    }
+
+    /// Deserializes a struct written by [`serialize`], keeping its mode.
+    pub fn deserialize<'de, S, D>(deserializer: D) -> Result<UniCase<S>, D::Error>
+    where
+        S: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("UniCase", FIELDS, ModeVisitor(PhantomData))
+    }
+
+    /// The field names, and the mode values, are both plain identifiers.
+    struct Ident(&'static [&'static str]);
+
+    impl<'de> Visitor<'de> for Ident {
+        type Value = usize;
+
+        fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            write!(fmt, "one of {:?}", self.0)
+        }
+
+        fn visit_str<E: de::Error>(self, s: &str) -> Result<usize, E> {
+            match self.0.iter().position(|&name| name == s) {
+                Some(i) => Ok(i),
+                None if self.0 == FIELDS => Err(E::unknown_field(s, FIELDS)),
+                None => Err(E::unknown_variant(s, MODES)),
+            }
+        }
+
+        fn visit_bytes<E: de::Error>(self, b: &[u8]) -> Result<usize, E> {
+            match core::str::from_utf8(b) {
+                Ok(s) => self.visit_str(s),
+                Err(_) => Err(E::invalid_value(de::Unexpected::Bytes(b), &self)),
+            }
+        }
+    }
+
+    struct Field(usize);
+
+    impl<'de> Deserialize<'de> for Field {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer
+                .deserialize_identifier(Ident(FIELDS))
+                .map(Field)
+        }
+    }
+
+    struct Ascii(bool);
+
+    impl<'de> Deserialize<'de> for Ascii {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer
+                .deserialize_str(Ident(MODES))
+                .map(|i| Ascii(i == 0))
+        }
+    }
+
+    struct ModeVisitor<S>(PhantomData<S>);
+
+    impl<S> ModeVisitor<S> {
+        fn build(ascii: bool, value: S) -> UniCase<S> {
+            if ascii {
+                UniCase::ascii(value)
+            } else {
+                UniCase::unicode(value)
+            }
+        }
+    }
+
+    impl<'de, S: Deserialize<'de>> Visitor<'de> for ModeVisitor<S> {
+        type Value = UniCase<S>;
+
+        fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            fmt.write_str("a UniCase with its mode")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let Ascii(ascii) = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            let value = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+            Ok(Self::build(ascii, value))
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut ascii = None;
+            let mut value = None;
+            while let Some(Field(field)) = map.next_key()? {
+                if field == 0 {
+                    if ascii.is_some() {
+                        return Err(de::Error::duplicate_field("mode"));
+                    }
+                    let Ascii(a) = map.next_value()?;
+                    ascii = Some(a);
+                } else {
+                    if value.is_some() {
+                        return Err(de::Error::duplicate_field("value"));
+                    }
+                    value = Some(map.next_value()?);
+                }
+            }
+            let ascii = ascii.ok_or_else(|| de::Error::missing_field("mode"))?;
+            let value = value.ok_or_else(|| de::Error::missing_field("value"))?;
+            Ok(Self::build(ascii, value))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Ascii, UniCase, UniCaseNoOpt};
+    use alloc::borrow::Cow;
+    use alloc::boxed::Box;
+    use serde::de::value::{BorrowedStrDeserializer, Error, StrDeserializer};
+    use serde::de::{Deserialize, IntoDeserializer};
+    use serde_derive::Deserialize;
+    use serde_test::{
+        assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, assert_tokens, Token,
+    };
+    use std::string::String;
+
+    #[test]
+    fn test_unicase_owned() {
+        let foo = UniCase::new(String::from("Foo"));
+        assert_tokens(&foo, &[Token::Str("Foo")]);
+        assert_de_tokens(&foo, &[Token::String("foo")]);
+        assert_de_tokens(&foo, &[Token::BorrowedStr("FOO")]);
+    }
+
+    #[test]
+    fn test_unicase_owned_coercions() {
+        assert_de_tokens(&UniCase::new(String::from("x")), &[Token::Char('x')]);
+        assert_de_tokens(&Ascii::new(String::from("X")), &[Token::Bytes(b"x")]);
+        assert_de_tokens_error::<UniCase<String>>(
+            &[Token::Bool(true)],
+            "invalid type: boolean `true`, expected a string",
+        );
+    }
+
+    #[cfg(feature = "serde-numbers")]
+    #[test]
+    fn test_numbers() {
+        use super::numbers;
+        use serde::de::value::{
+            BoolDeserializer, F64Deserializer, I8Deserializer, U64Deserializer,
+        };
+
+        let n: UniCase<String> = numbers::deserialize(U64Deserializer::<Error>::new(42)).unwrap();
+        assert_eq!(n, UniCase::new("42"));
+        let n: UniCase<String> = numbers::deserialize(I8Deserializer::<Error>::new(-7)).unwrap();
+        assert_eq!(n, UniCase::new("-7"));
+        let n: Ascii<String> =
+            numbers::deserialize_ascii(F64Deserializer::<Error>::new(1.5)).unwrap();
+        assert_eq!(n, Ascii::new("1.5"));
+        let s: UniCase<String> =
+            numbers::deserialize(StrDeserializer::<Error>::new("Foo")).unwrap();
+        assert_eq!(s, UniCase::new("FOO"));
+        assert!(numbers::deserialize::<String, _>(BoolDeserializer::<Error>::new(true)).is_err());
+
+        #[derive(Deserialize)]
+        struct Route {
+            #[serde(deserialize_with = "numbers::deserialize")]
+            status: UniCase<String>,
+            #[serde(deserialize_with = "numbers::deserialize_ascii")]
+            method: Ascii<String>,
+        }
+        let routes: std::vec::Vec<Route> = serde_json::from_str(
+            r#"[{"status": 404, "method": "GET"}, {"status": "2xx", "method": 7}]"#,
+        )
+        .unwrap();
+        assert_eq!(routes[0].status, UniCase::new("404"));
+        assert_eq!(routes[0].method, Ascii::new("get"));
+        assert_eq!(routes[1].status, UniCase::new("2XX"));
+        assert_eq!(routes[1].method, Ascii::new("7"));
+    }
+
+    #[test]
+    fn test_unicase_owned_rejects_numbers() {
+        assert_de_tokens_error::<UniCase<String>>(
+            &[Token::U64(42)],
+            "invalid type: integer `42`, expected a string",
+        );
+        assert_de_tokens_error::<Ascii<String>>(
+            &[Token::F64(1.5)],
+            "invalid type: floating point `1.5`, expected a string",
+        );
+    }
+
+    #[test]
+    fn test_unicase_borrowed() {
+        let foo = UniCase::new("Foo");
+        assert_tokens(&foo, &[Token::BorrowedStr("Foo")]);
+        assert_de_tokens_error::<UniCase<&str>>(
+            &[Token::Str("Foo")],
+            "invalid type: string \"Foo\", expected a borrowed string",
+        );
+    }
+
+    #[cfg(feature = "compact_str")]
+    #[test]
+    fn test_compact_string() {
+        use crate::hash::hash_of;
+        use compact_str::CompactString;
+
+        let foo = UniCase::new(CompactString::from("Foo"));
+        assert_tokens(&foo, &[Token::Str("Foo")]);
+        assert_de_tokens(&foo, &[Token::String("FOO")]);
+        assert_de_tokens(&foo, &[Token::BorrowedStr("foo")]);
+        assert_de_tokens(
+            &Ascii::new(CompactString::from("Foo")),
+            &[Token::Str("fOO")],
+        );
+
+        // round trips, and agrees with `String` keys; the long value is
+        // too big to be stored inline
+        let long = "Content-Security-Policy-Report-Only";
+        for &s in &["Maße", long, ""] {
+            let key = UniCase::new(CompactString::from(s));
+            let json = serde_json::to_string(&key).unwrap();
+            let back: UniCase<CompactString> = serde_json::from_str(&json).unwrap();
+            let string: UniCase<String> = serde_json::from_str(&json).unwrap();
+            assert_eq!(back.as_ref(), s);
+            assert_eq!(back, string);
+            assert_eq!(hash_of(&back), hash_of(&string));
+            assert_eq!(back.is_ascii(), string.is_ascii());
+        }
+        assert!(CompactString::from(long).is_heap_allocated());
+
+        let map: std::collections::HashMap<UniCase<CompactString>, u32> =
+            serde_json::from_str(r#"{"Accept": 1, "MASSE": 2}"#).unwrap();
+        assert_eq!(map[&UniCase::new(CompactString::from("maße"))], 2);
+    }
+
+    #[test]
+    fn test_unicase_cow() {
+        let foo = UniCase::new(Cow::from("Foo"));
+        assert_tokens(&foo, &[Token::Str("Foo")]);
+        assert_de_tokens(&foo, &[Token::BorrowedStr("foo")]);
+        assert_de_tokens(&foo, &[Token::String("FOO")]);
+    }
+
+    #[test]
+    fn test_unicase_cow_borrows() {
+        let de = BorrowedStrDeserializer::<Error>::new("Foo");
+        match UniCase::<Cow<str>>::deserialize(de).unwrap().into_inner() {
+            Cow::Borrowed(s) => assert_eq!(s, "Foo"),
+            Cow::Owned(_) => panic!("expected a borrowed string"),
+        }
+    }
+
+    #[test]
+    fn test_cow_borrows_through_derive() {
+        #[derive(Deserialize)]
+        struct Headers<'a> {
+            #[serde(borrow)]
+            name: Ascii<Cow<'a, str>>,
+            #[serde(borrow)]
+            value: UniCase<Cow<'a, str>>,
+        }
+
+        let json = r#"{ "name": "Content-Type", "value": "Text/HTML" }"#;
+        let headers: Headers = serde_json::from_str(json).unwrap();
+        assert_eq!(headers.name, "content-type");
+        assert_eq!(headers.value, UniCase::new("text/html"));
+        assert!(matches!(headers.name.into_inner(), Cow::Borrowed(_)));
+        assert!(matches!(headers.value.into_inner(), Cow::Borrowed(_)));
+
+        // escapes can't be borrowed, so those still allocate
+        let json = r#"{ "name": "Content\u002dType", "value": "Text/HTML" }"#;
+        let headers: Headers = serde_json::from_str(json).unwrap();
+        assert_eq!(headers.name, "content-type");
+        assert!(matches!(headers.name.into_inner(), Cow::Owned(_)));
+    }
+
+    #[test]
+    fn test_buffered_content() {
+        use serde_derive::Deserialize;
+
+        // internally tagged and untagged enums buffer their input, and hand
+        // it back through serde's private `Content` deserializers
+        #[derive(Debug, Deserialize)]
+        #[serde(tag = "type")]
+        enum Message<'a> {
+            Header {
+                name: UniCase<String>,
+                #[serde(borrow)]
+                value: Ascii<Cow<'a, str>>,
+            },
+            Ping {
+                id: Ascii<&'a str>,
+            },
+        }
+
+        let json = r#"{ "type": "Header", "name": "Content-Type", "value": "Text/HTML" }"#;
+        match serde_json::from_str(json).unwrap() {
+            Message::Header { name, value } => {
+                assert_eq!(name, UniCase::new("content-type"));
+                assert_eq!(value, "text/html");
+            }
+            other => panic!("{:?}", other),
+        }
+        let json = r#"{ "id": "ABC", "type": "Ping" }"#;
+        match serde_json::from_str(json).unwrap() {
+            Message::Ping { id } => assert_eq!(id, "abc"),
+            other => panic!("{:?}", other),
+        }
+
+        #[derive(Debug, Deserialize)]
+        #[serde(untagged)]
+        enum Key<'a> {
+            Id(u32),
+            Name(#[serde(borrow)] UniCase<Cow<'a, str>>),
+        }
+        match serde_json::from_str(r#""Maße""#).unwrap() {
+            Key::Name(key) => {
+                assert_eq!(key, UniCase::new("MASSE"));
+                assert!(matches!(key.into_inner(), Cow::Borrowed(_)));
+            }
+            other => panic!("{:?}", other),
+        }
+        match serde_json::from_str("7").unwrap() {
+            Key::Id(id) => assert_eq!(id, 7),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_map_keys() {
+        use std::collections::{BTreeMap, HashMap};
+
+        let json = r#"{ "Accept": 1, "HOST": 2 }"#;
+        let map: HashMap<UniCase<String>, u32> = serde_json::from_str(json).unwrap();
+        assert_eq!(map[&UniCase::new(String::from("host"))], 2);
+        let map: BTreeMap<Ascii<Cow<str>>, u32> = serde_json::from_str(json).unwrap();
+        assert_eq!(map[&Ascii::new(Cow::from("accept"))], 1);
+    }
+
+    #[test]
+    fn test_json_value_map() {
+        use serde_json::{json, Value};
+        use std::collections::HashMap;
+
+        // later duplicates replace the value, but the first casing is kept;
+        // the escaped key comes through `visit_string` rather than
+        // `visit_borrowed_str`
+        let json = r#"{
+            "Content-Type": "text/plain",
+            "Accept": ["*/*"],
+            "CONTENT-TYPE": {"nested": true},
+            "Stra\u00dfe": null,
+            "STRASSE": 1
+        }"#;
+        let map: HashMap<UniCase<String>, Value> = serde_json::from_str(json).unwrap();
+        assert_eq!(map.len(), 3);
+        let get = |k: &str| map.get(&UniCase::new(String::from(k)));
+        assert_eq!(get("content-type"), Some(&json!({"nested": true})));
+        assert_eq!(get("ACCEPT"), Some(&json!(["*/*"])));
+        assert_eq!(get("strasse"), Some(&json!(1)));
+        assert_eq!(get("Straße"), Some(&json!(1)));
+        let mut keys: std::vec::Vec<&str> = map.keys().map(|k| k.as_ref()).collect();
+        keys.sort();
+        assert_eq!(keys, ["Accept", "Content-Type", "Straße"]);
+
+        // the same through a `Value`'s own deserializer, and through the
+        // buffered keys of a flattened map; a `Value` keeps its object
+        // sorted by exact key, so there "CONTENT-TYPE" comes first
+        let value: Value = serde_json::from_str(json).unwrap();
+        let from_value: HashMap<UniCase<String>, Value> =
+            serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(from_value.len(), 3);
+        let (k, v) = from_value
+            .get_key_value(&UniCase::new(String::from("CONTENT-type")))
+            .unwrap();
+        assert_eq!((k.as_ref(), v), ("CONTENT-TYPE", &json!("text/plain")));
+
+        #[derive(Deserialize)]
+        struct Headers {
+            #[serde(rename = "Accept")]
+            _accept: Value,
+            #[serde(flatten)]
+            rest: HashMap<UniCase<String>, Value>,
+        }
+        let headers: Headers = serde_json::from_value(value).unwrap();
+        assert_eq!(headers.rest.len(), 2);
+        assert!(headers
+            .rest
+            .contains_key(&UniCase::new(String::from("strasse"))));
+    }
+
+    #[test]
+    fn test_identifier() {
+        use serde::de::value::{BorrowedBytesDeserializer, BytesDeserializer};
+
+        // some formats hand out strings as bytes, as for identifiers
+        let de = BorrowedBytesDeserializer::<Error>::new(b"Foo");
+        match UniCase::<Cow<str>>::deserialize(de).unwrap().into_inner() {
+            Cow::Borrowed(s) => assert_eq!(s, "Foo"),
+            Cow::Owned(_) => panic!("expected a borrowed string"),
+        }
+        let de = BytesDeserializer::<Error>::new(b"Foo");
+        assert_eq!(
+            Ascii::<Cow<str>>::deserialize(de).unwrap(),
+            Ascii::new("FOO")
+        );
+        let de = BytesDeserializer::<Error>::new(b"Foo\xFF");
+        assert!(UniCase::<Cow<str>>::deserialize(de).is_err());
+
+        let de = BytesDeserializer::<Error>::new(b"Foo");
+        assert_eq!(
+            UniCase::<String>::deserialize(de).unwrap(),
+            UniCase::new("FOO")
+        );
+    }
+
+    #[test]
+    fn test_unicase_mode() {
+        let de = |s: &str| {
+            let de: StrDeserializer<Error> = s.into_deserializer();
+            UniCase::<String>::deserialize(de).unwrap()
+        };
+        assert!(de("Foo").is_ascii());
+        assert!(!de("Maße").is_ascii());
+        assert_eq!(de("Maße"), UniCase::new("MASSE"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_sorted_map() {
+        use serde_derive::Serialize;
+        use std::collections::HashMap;
+        use std::string::ToString;
+
+        #[derive(Serialize)]
+        struct Counts {
+            #[serde(serialize_with = "crate::serde::sorted_map")]
+            counts: HashMap<UniCase<String>, u32>,
+        }
+
+        let keys = ["b", "Maße", "A", "c", "Zeta", "ä", "B2"];
+        let expected = r#"{"counts":{"A":2,"b":0,"B2":6,"c":3,"Maße":1,"Zeta":4,"ä":5}}"#;
+        // the same map built in different orders serializes the same way
+        for rotation in 0..keys.len() {
+            let mut counts = HashMap::new();
+            for i in 0..keys.len() {
+                let i = (i + rotation) % keys.len();
+                counts.insert(UniCase::new(keys[i].to_string()), i as u32);
+            }
+            let json = serde_json::to_string(&Counts { counts }).unwrap();
+            assert_eq!(json, expected);
+        }
+    }
+
+    #[test]
+    fn test_ascii_bytes_owned() {
+        use crate::AsciiBytes;
+        use std::vec::Vec;
+
+        let key = AsciiBytes::new(Vec::from(&b"Subject\xFF"[..]));
+        assert_tokens(&key, &[Token::Bytes(b"Subject\xFF")]);
+        assert_de_tokens(&key, &[Token::ByteBuf(b"SUBJECT\xFF")]);
+        assert_de_tokens(&key, &[Token::BorrowedBytes(b"subject\xFF")]);
+        assert_de_tokens(
+            &AsciiBytes::new(Vec::from(&b"To"[..])),
+            &[
+                Token::Seq { len: Some(2) },
+                Token::U8(b't'),
+                Token::U8(b'O'),
+                Token::SeqEnd,
+            ],
+        );
+        assert_de_tokens(&AsciiBytes::new(Vec::from(&b"To"[..])), &[Token::Str("tO")]);
+
+        // the original casing survives a round trip through a real format
+        let json = serde_json::to_string(&key).unwrap();
+        let back: AsciiBytes<Vec<u8>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.as_ref(), b"Subject\xFF");
+    }
+
+    #[test]
+    fn test_ascii_bytes_borrowed() {
+        use crate::AsciiBytes;
+
+        let key = AsciiBytes::new(&b"From"[..]);
+        assert_tokens(&key, &[Token::BorrowedBytes(b"From")]);
+        assert_de_tokens(&key, &[Token::BorrowedBytes(b"FROM")]);
+    }
+
+    #[test]
+    fn test_ascii_owned() {
+        let foo = Ascii::new(String::from("Foo"));
+        assert_tokens(&foo, &[Token::Str("Foo")]);
+        assert_de_tokens(&foo, &[Token::String("foo")]);
+        assert_de_tokens(&foo, &[Token::BorrowedStr("FOO")]);
+    }
+
+    #[test]
+    fn test_no_opt_owned() {
+        let foo = UniCaseNoOpt::new(String::from("Maße"));
+        assert_tokens(&foo, &[Token::Str("Maße")]);
+        assert_de_tokens(&foo, &[Token::String("MASSE")]);
+        assert_de_tokens(&foo, &[Token::BorrowedStr("masse")]);
+    }
+
+    #[test]
+    fn test_no_opt_boxed() {
+        let foo: Box<UniCaseNoOpt<str>> = Box::from(UniCaseNoOpt::from_ref("Maße"));
+        assert_tokens(&foo, &[Token::Str("Maße")]);
+        assert_de_tokens(&foo, &[Token::String("MASSE")]);
+        assert_de_tokens(&foo, &[Token::BorrowedStr("masse")]);
+        assert_ser_tokens(&UniCaseNoOpt::from_ref("Foo"), &[Token::Str("Foo")]);
+    }
+
+    #[test]
+    fn test_ascii_borrowed() {
+        let foo = Ascii::new("Foo");
+        assert_tokens(&foo, &[Token::BorrowedStr("Foo")]);
+        assert_de_tokens_error::<Ascii<&str>>(
+            &[Token::Str("Foo")],
+            "invalid type: string \"Foo\", expected a borrowed string",
+        );
+    }
+
+    #[test]
+    fn test_ascii_cow() {
+        let foo = Ascii::new(Cow::from("Foo"));
+        assert_tokens(&foo, &[Token::Str("Foo")]);
+        assert_de_tokens(&foo, &[Token::BorrowedStr("foo")]);
+        assert_de_tokens(&foo, &[Token::String("FOO")]);
+    }
+
+    #[test]
+    fn test_trimmed() {
+        #[derive(Debug, Deserialize)]
+        struct Config {
+            #[serde(deserialize_with = "crate::serde::trimmed::deserialize")]
+            key: UniCase<String>,
+        }
+
+        let config: Config = serde_json::from_str(r#"{ "key": "  Foo  " }"#).unwrap();
+        assert_eq!(config.key, UniCase::new("foo"));
+        assert_eq!(config.key.as_ref(), "Foo");
+        assert!(config.key.is_ascii());
+
+        let config: Config = serde_json::from_str(r#"{ "key": "\u3000Maße\n" }"#).unwrap();
+        assert_eq!(config.key.as_ref(), "Maße");
+        assert!(!config.key.is_ascii());
+
+        let de: StrDeserializer<Error> = "Host".into_deserializer();
+        let key = crate::serde::trimmed::deserialize(de).unwrap();
+        assert_eq!(key, UniCase::new("HOST"));
+        assert!(serde_json::from_str::<Config>(r#"{ "key": 1 }"#).is_err());
+    }
+
+    #[cfg(feature = "serde-mode")]
+    #[test]
+    fn test_with_mode() {
+        use serde_derive::{Deserialize, Serialize};
+        use std::string::ToString;
+
+        #[derive(Debug, Serialize, Deserialize)]
+        struct Key {
+            #[serde(with = "crate::serde::with_mode")]
+            name: UniCase<String>,
+        }
+
+        // forced ASCII on non-ASCII text would come back as Unicode by default
+        let key = Key {
+            name: UniCase::ascii(String::from("Äb")),
+        };
+        let json = serde_json::to_string(&key).unwrap();
+        assert_eq!(json, r#"{"name":{"mode":"ascii","value":"Äb"}}"#);
+        let back: Key = serde_json::from_str(&json).unwrap();
+        assert!(back.name.is_ascii());
+        assert_eq!(back.name.as_ref(), "Äb");
+        let plain: UniCase<String> = serde_json::from_str(r#""Äb""#).unwrap();
+        assert!(!plain.is_ascii());
+
+        let forced: Key =
+            serde_json::from_str(r#"{"name":{"value":"ab","mode":"unicode"}}"#).unwrap();
+        assert!(!forced.name.is_ascii());
+
+        let err = serde_json::from_str::<Key>(r#"{"name":{"mode":"latin1","value":"a"}}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown variant `latin1`"), "{}", err);
+        let err = serde_json::from_str::<Key>(r#"{"name":{"mode":"ascii"}}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("missing field `value`"), "{}", err);
+    }
+
+    #[cfg(feature = "serde-mode")]
+    #[test]
+    fn test_with_mode_seq() {
+        use serde::de::value::SeqDeserializer;
+
+        // formats without field names send the fields in order
+        let de = SeqDeserializer::<_, Error>::new(["unicode", "ab"].iter().cloned());
+        let value: UniCase<String> = crate::serde::with_mode::deserialize(de).unwrap();
+        assert!(!value.is_ascii());
+        assert_eq!(value, UniCase::new("AB"));
+    }
+}
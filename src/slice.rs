@@ -0,0 +1,236 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use super::UniCase;
+
+/// Sorts strings case-insensitively, folding each one only once.
+///
+/// This gives the same order as
+/// `items.sort_by(|a, b| UniCase::new(a).cmp(&UniCase::new(b)))`, including
+/// keeping strings that compare equal in their original order. That naive
+/// sort folds both sides of every comparison, so O(n log n) times in all.
+/// Here the folded key of each string is computed up front, a list of
+/// indices is sorted by those keys, and the strings are then moved into
+/// place.
+///
+/// This trades memory for speed: while sorting, it holds a folded copy of
+/// every string plus one `usize` per item.
+///
+/// # Example
+///
+/// ```rust
+/// let mut items = vec!["b".to_owned(), "Straße".to_owned(), "A".to_owned()];
+/// unicase::sort_by_folded_key(&mut items);
+/// assert_eq!(items, ["A", "b", "Straße"]);
+/// ```
+pub fn sort_by_folded_key(items: &mut [String]) {
+    let keys: Vec<String> = items
+        .iter()
+        .map(|s| UniCase::new(s.as_str()).to_folded_case())
+        .collect();
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    // UTF-8 bytes sort in the same order as the chars they encode
+    order.sort_by(|&a, &b| keys[a].cmp(&keys[b]));
+    drop(keys);
+
+    // `order[i]` is the index of the item that belongs at `i`; walk each
+    // cycle of the permutation, marking positions done as they are filled
+    for start in 0..order.len() {
+        let mut cur = start;
+        while order[cur] != cur {
+            let next = order[cur];
+            order[cur] = cur;
+            if next == start {
+                break;
+            }
+            items.swap(cur, next);
+            cur = next;
+        }
+    }
+}
+
+/// Removes consecutive strings that are equal ignoring case, keeping the
+/// first of each run.
+///
+/// This is `Vec::dedup` with `UniCase` equality, so it runs in O(n) and
+/// only removes duplicates that are next to each other. After sorting with
+/// [`sort_by_folded_key`], equal strings are always adjacent, so this leaves
+/// one of each. Each pair is compared without allocating.
+///
+/// # Example
+///
+/// ```rust
+/// let mut v = vec!["Apple".to_owned(), "APPLE".to_owned(), "banana".to_owned()];
+/// unicase::dedup_sorted_ci(&mut v);
+/// assert_eq!(v, ["Apple", "banana"]);
+/// ```
+pub fn dedup_sorted_ci<S: AsRef<str>>(v: &mut Vec<S>) {
+    // `dedup_by` passes the later item first, and removes it if equal
+    v.dedup_by(|later, kept| UniCase::new(later.as_ref()) == UniCase::new(kept.as_ref()));
+}
+
+/// Returns the case-insensitively smallest string, or `None` if `items` is
+/// empty.
+///
+/// Strings are ordered as by `UniCase`'s `Ord`, which compares folded chars
+/// without allocating. If several are equally smallest, the first of them is
+/// returned. The result borrows from `items`, with its original casing.
+///
+/// # Example
+///
+/// ```rust
+/// assert_eq!(unicase::min_ci(&["banana", "Apple", "cherry"]), Some("Apple"));
+/// ```
+pub fn min_ci<S: AsRef<str>>(items: &[S]) -> Option<&str> {
+    first_by(items, Ordering::Less)
+}
+
+/// Returns the case-insensitively largest string, or `None` if `items` is
+/// empty.
+///
+/// Like [`min_ci`], if several are equally largest, the first of them is
+/// returned.
+///
+/// # Example
+///
+/// ```rust
+/// assert_eq!(unicase::max_ci(&["banana", "Apple", "cherry"]), Some("cherry"));
+/// ```
+pub fn max_ci<S: AsRef<str>>(items: &[S]) -> Option<&str> {
+    first_by(items, Ordering::Greater)
+}
+
+/// Returns the first of the items that compare `wanted` (`Less` for the
+/// minimum, `Greater` for the maximum) against all others.
+fn first_by<S: AsRef<str>>(items: &[S], wanted: Ordering) -> Option<&str> {
+    let mut iter = items.iter().map(AsRef::as_ref);
+    let mut best = iter.next()?;
+    for s in iter {
+        if UniCase::new(s).cmp(&UniCase::new(best)) == wanted {
+            best = s;
+        }
+    }
+    Some(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dedup_sorted_ci, max_ci, min_ci, sort_by_folded_key};
+    use crate::UniCase;
+    use std::string::{String, ToString};
+    use std::vec::Vec;
+
+    fn naive(items: &mut [String]) {
+        items.sort_by(|a, b| UniCase::new(a).cmp(&UniCase::new(b)));
+    }
+
+    fn words(n: usize) -> Vec<String> {
+        const PARTS: &[&str] = &["a", "B", "ß", "SS", "Σ", "σ", "é", "E", "z", "ﬃ"];
+        let mut seed = 0x2545_F491_u32;
+        (0..n)
+            .map(|_| {
+                let mut s = String::new();
+                for _ in 0..(seed % 4 + 1) {
+                    seed ^= seed << 13;
+                    seed ^= seed >> 17;
+                    seed ^= seed << 5;
+                    s.push_str(PARTS[seed as usize % PARTS.len()]);
+                }
+                s
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_by_folded_key() {
+        let mut items: Vec<String> = ["Maße", "b", "MASSE", "a", "masse", "A"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        sort_by_folded_key(&mut items);
+        // equal strings keep their original order
+        assert_eq!(items, ["a", "A", "b", "Maße", "MASSE", "masse"]);
+
+        let mut empty: Vec<String> = Vec::new();
+        sort_by_folded_key(&mut empty);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_sort_by_folded_key_matches_naive() {
+        for &n in &[1, 2, 7, 100, 1000] {
+            let mut expected = words(n);
+            let mut actual = expected.clone();
+            naive(&mut expected);
+            sort_by_folded_key(&mut actual);
+            assert_eq!(actual, expected, "{} items", n);
+        }
+    }
+
+    #[test]
+    fn test_dedup_sorted_ci() {
+        let mut v: Vec<String> = ["Apple", "APPLE", "banana"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        dedup_sorted_ci(&mut v);
+        assert_eq!(v, ["Apple", "banana"]);
+
+        let mut v: Vec<String> = ["Maße", "a", "A", "MASSE", "masse", "b", "B", "A"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        sort_by_folded_key(&mut v);
+        dedup_sorted_ci(&mut v);
+        assert_eq!(v, ["a", "b", "Maße"]);
+
+        // only neighbours are merged
+        let mut v = std::vec!["x", "y", "X"];
+        dedup_sorted_ci(&mut v);
+        assert_eq!(v, ["x", "y", "X"]);
+
+        let mut empty: Vec<&str> = Vec::new();
+        dedup_sorted_ci(&mut empty);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_min_max_ci() {
+        let fruit = ["banana", "Apple", "cherry"];
+        assert_eq!(min_ci(&fruit), Some("Apple"));
+        assert_eq!(max_ci(&fruit), Some("cherry"));
+
+        // the first of several equal strings wins
+        let ties = [String::from("b"), String::from("A"), String::from("a")];
+        assert_eq!(min_ci(&ties), Some("A"));
+        let ties = ["MASSE", "a", "Maße", "masse"];
+        assert_eq!(max_ci(&ties), Some("MASSE"));
+
+        let empty: [&str; 0] = [];
+        assert_eq!(min_ci(&empty), None);
+        assert_eq!(max_ci(&empty), None);
+    }
+
+    #[cfg(feature = "nightly")]
+    #[bench]
+    fn bench_sort_by_folded_key_100k(b: &mut ::test::Bencher) {
+        let items = words(100_000);
+        b.iter(|| {
+            let mut items = items.clone();
+            sort_by_folded_key(&mut items);
+            items
+        });
+    }
+
+    #[cfg(feature = "nightly")]
+    #[bench]
+    fn bench_sort_naive_100k(b: &mut ::test::Bencher) {
+        let items = words(100_000);
+        b.iter(|| {
+            let mut items = items.clone();
+            naive(&mut items);
+            items
+        });
+    }
+}
@@ -0,0 +1,228 @@
+use alloc::string::String;
+use core::fmt;
+use core::ops::Deref;
+use core::str;
+
+use super::{FoldedChars, UniCase};
+
+const INLINE_CAPACITY: usize = 23;
+
+/// A case-folded string that is stored inline when it is short.
+///
+/// Folded forms of up to [`SmallFolded::INLINE_CAPACITY`] bytes (enough for
+/// nearly every HTTP header name) live in the value itself, so producing one
+/// does not allocate. Longer forms spill to a heap `String`.
+///
+/// Returned by [`UniCase::small_folded`].
+#[derive(Clone)]
+pub struct SmallFolded(Repr);
+
+#[derive(Clone)]
+enum Repr {
+    Inline { len: u8, buf: [u8; INLINE_CAPACITY] },
+    Heap(String),
+}
+
+impl SmallFolded {
+    /// The longest folded form, in bytes, that is stored without allocating.
+    pub const INLINE_CAPACITY: usize = INLINE_CAPACITY;
+
+    fn from_chars(mut chars: FoldedChars) -> SmallFolded {
+        let mut len = 0;
+        let mut buf = [0; INLINE_CAPACITY];
+        while let Some(c) = chars.next() {
+            let width = c.len_utf8();
+            if len + width > INLINE_CAPACITY {
+                let mut heap = String::with_capacity(INLINE_CAPACITY * 2);
+                heap.push_str(str::from_utf8(&buf[..len]).expect("inline buffer is UTF-8"));
+                heap.push(c);
+                heap.extend(chars);
+                return SmallFolded(Repr::Heap(heap));
+            }
+            c.encode_utf8(&mut buf[len..]);
+            len += width;
+        }
+        SmallFolded(Repr::Inline {
+            len: len as u8,
+            buf,
+        })
+    }
+
+    /// Returns the folded string.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        match self.0 {
+            // only ever filled with whole encoded chars
+            Repr::Inline { len, ref buf } => {
+                str::from_utf8(&buf[..len as usize]).expect("inline buffer is UTF-8")
+            }
+            Repr::Heap(ref s) => s,
+        }
+    }
+
+    /// Returns `true` if the folded string is stored inline.
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        match self.0 {
+            Repr::Inline { .. } => true,
+            Repr::Heap(_) => false,
+        }
+    }
+}
+
+impl<S: AsRef<str>> UniCase<S> {
+    /// Returns the case-folded form of this string, without allocating if it
+    /// is short.
+    ///
+    /// The folding is the same as [`to_folded_case`](UniCase::to_folded_case),
+    /// but the result is kept inline when it is at most
+    /// [`SmallFolded::INLINE_CAPACITY`] bytes long.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use unicase::UniCase;
+    ///
+    /// let folded = UniCase::new("Content-Type").small_folded();
+    /// assert_eq!(folded.as_str(), "content-type");
+    /// assert!(folded.is_inline());
+    /// ```
+    pub fn small_folded(&self) -> SmallFolded {
+        SmallFolded::from_chars(FoldedChars::new(self.as_ref(), self.folds_as_ascii()))
+    }
+}
+
+impl Deref for SmallFolded {
+    type Target = str;
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for SmallFolded {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<[u8]> for SmallFolded {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_str().as_bytes()
+    }
+}
+
+impl fmt::Debug for SmallFolded {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), fmt)
+    }
+}
+
+impl fmt::Display for SmallFolded {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), fmt)
+    }
+}
+
+impl PartialEq for SmallFolded {
+    #[inline]
+    fn eq(&self, other: &SmallFolded) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for SmallFolded {}
+
+impl PartialEq<str> for SmallFolded {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for SmallFolded {
+    #[inline]
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SmallFolded;
+    use crate::UniCase;
+
+    #[test]
+    fn test_small_folded_inline() {
+        let folded = UniCase::new("Content-Type").small_folded();
+        assert_eq!(folded, "content-type");
+        assert!(folded.is_inline());
+
+        let folded = UniCase::new("Maße").small_folded();
+        assert_eq!(folded, "masse");
+        assert!(folded.is_inline());
+
+        assert_eq!(UniCase::new("").small_folded(), "");
+    }
+
+    #[test]
+    fn test_small_folded_boundary() {
+        let exact = "A".repeat(SmallFolded::INLINE_CAPACITY);
+        let folded = UniCase::new(&*exact).small_folded();
+        assert!(folded.is_inline());
+        assert_eq!(folded, &*exact.to_lowercase());
+
+        let over = "A".repeat(SmallFolded::INLINE_CAPACITY + 1);
+        let folded = UniCase::new(&*over).small_folded();
+        assert!(!folded.is_inline());
+        assert_eq!(folded, &*over.to_lowercase());
+
+        // a multi-byte char that would straddle the limit spills whole
+        let straddle = "a".repeat(SmallFolded::INLINE_CAPACITY - 1) + "Σ";
+        let folded = UniCase::new(&*straddle).small_folded();
+        assert!(!folded.is_inline());
+        assert_eq!(folded, &*UniCase::new(&*straddle).to_folded_case());
+    }
+
+    #[test]
+    fn test_small_folded_matches_to_folded_case() {
+        for &s in &["FooBar", "ΣΊΣΥΦΟΣ", "Ünïcödé FÖÖBÄR Straße ΣΊΣΥΦΟΣ"] {
+            let a = UniCase::new(s);
+            assert_eq!(a.small_folded(), &*a.to_folded_case());
+        }
+        // non-ASCII text gets the full fold, even in ASCII mode
+        assert_eq!(UniCase::ascii("ÄB").small_folded(), "äb");
+    }
+
+    #[cfg(feature = "nightly")]
+    #[bench]
+    fn bench_small_folded_short(b: &mut ::test::Bencher) {
+        let x = UniCase::new("Content-Type");
+        b.iter(|| x.small_folded());
+    }
+
+    #[cfg(feature = "nightly")]
+    #[bench]
+    fn bench_to_folded_case_short(b: &mut ::test::Bencher) {
+        let x = UniCase::new("Content-Type");
+        b.iter(|| x.to_folded_case());
+    }
+
+    #[cfg(feature = "nightly")]
+    #[bench]
+    fn bench_small_folded_short_unicode(b: &mut ::test::Bencher) {
+        let x = UniCase::new("Straße");
+        b.iter(|| x.small_folded());
+    }
+
+    #[cfg(feature = "nightly")]
+    #[bench]
+    fn bench_to_folded_case_short_unicode(b: &mut ::test::Bencher) {
+        let x = UniCase::new("Straße");
+        b.iter(|| x.to_folded_case());
+    }
+}
@@ -0,0 +1,212 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::str;
+use std::io::{self, Read};
+
+use super::unicode::{lookup, Fold};
+
+const CHUNK: usize = 8 * 1024;
+
+/// Returns whether two UTF-8 streams are equal, ignoring case.
+///
+/// Both readers are consumed in chunks and folded as they go, so neither is
+/// ever fully loaded into memory. Chunk edges may fall anywhere: in the
+/// middle of a char's bytes, or between the chars a fold expands to (`"ß"`
+/// in one stream against `"SS"` split across two reads of the other).
+///
+/// This uses full Unicode case folding, so it agrees with comparing
+/// `UniCase::new` on the whole contents. Reading stops at the first
+/// difference.
+///
+/// Only available with the `std` feature.
+///
+/// # Errors
+///
+/// Returns any error from either reader, other than `Interrupted`, which is
+/// retried. Input that is not valid UTF-8 is an `InvalidData` error.
+///
+/// # Example
+///
+/// ```rust
+/// let a = "Straße".repeat(1000);
+/// let b = "STRASSE".repeat(1000);
+/// assert!(unicase::streams_eq_ci(a.as_bytes(), b.as_bytes()).unwrap());
+/// ```
+pub fn streams_eq_ci<R1: Read, R2: Read>(a: R1, b: R2) -> io::Result<bool> {
+    let mut a = FoldedReader::new(a);
+    let mut b = FoldedReader::new(b);
+    loop {
+        match (a.next()?, b.next()?) {
+            (None, None) => return Ok(true),
+            (x, y) if x == y => (),
+            _ => return Ok(false),
+        }
+    }
+}
+
+/// Decodes and folds chars from a reader, one at a time.
+struct FoldedReader<R> {
+    inner: R,
+    buf: Vec<u8>,
+    start: usize,
+    end: usize,
+    pending: Fold,
+}
+
+impl<R: Read> FoldedReader<R> {
+    fn new(inner: R) -> FoldedReader<R> {
+        FoldedReader {
+            inner,
+            buf: vec![0; CHUNK],
+            start: 0,
+            end: 0,
+            pending: Fold::Zero,
+        }
+    }
+
+    fn next(&mut self) -> io::Result<Option<char>> {
+        if let Some(c) = self.pending.next() {
+            return Ok(Some(c));
+        }
+        match self.next_char()? {
+            Some(c) => {
+                self.pending = lookup(c);
+                Ok(self.pending.next())
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_char(&mut self) -> io::Result<Option<char>> {
+        if self.start == self.end && !self.fill()? {
+            return Ok(None);
+        }
+        let width = match self.buf[self.start] {
+            0x00..=0x7F => 1,
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => return Err(invalid_utf8()),
+        };
+        // the rest of this char's bytes may still be in the reader
+        while self.end - self.start < width {
+            if !self.fill()? {
+                return Err(invalid_utf8());
+            }
+        }
+        let bytes = &self.buf[self.start..self.start + width];
+        let c = match str::from_utf8(bytes) {
+            Ok(s) => s.chars().next().expect("width is at least 1"),
+            Err(_) => return Err(invalid_utf8()),
+        };
+        self.start += width;
+        Ok(Some(c))
+    }
+
+    /// Reads more bytes after any that are left over, returning `false` at
+    /// the end of the stream.
+    fn fill(&mut self) -> io::Result<bool> {
+        if self.start > 0 {
+            self.buf.copy_within(self.start..self.end, 0);
+            self.end -= self.start;
+            self.start = 0;
+        }
+        loop {
+            match self.inner.read(&mut self.buf[self.end..]) {
+                Ok(0) => return Ok(false),
+                Ok(n) => {
+                    self.end += n;
+                    return Ok(true);
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => (),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+fn invalid_utf8() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "stream did not contain valid UTF-8",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::streams_eq_ci;
+    use std::io::{self, Read};
+    use std::string::String;
+
+    /// Hands out at most `size` bytes per read.
+    struct Chunked<'a> {
+        data: &'a [u8],
+        size: usize,
+    }
+
+    impl<'a> Read for Chunked<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.size.min(buf.len()).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    fn chunked(data: &str, size: usize) -> Chunked<'_> {
+        Chunked {
+            data: data.as_bytes(),
+            size,
+        }
+    }
+
+    #[test]
+    fn test_streams_eq_ci_large() {
+        let a = "Hello, Wörld! Ünïcödé Straße. ".repeat(500);
+        let b = a.to_uppercase();
+        assert!(a.len() > 10 * 1024);
+        assert!(streams_eq_ci(a.as_bytes(), b.as_bytes()).unwrap());
+
+        let mut c = b.clone();
+        c.push('x');
+        assert!(!streams_eq_ci(a.as_bytes(), c.as_bytes()).unwrap());
+
+        let mut d = b.into_bytes();
+        let mid = d.len() / 2;
+        d[mid] = b'#';
+        assert!(!streams_eq_ci(a.as_bytes(), &d[..]).unwrap());
+    }
+
+    #[test]
+    fn test_streams_eq_ci_straddling() {
+        // every split point, through the bytes of `ß` and `Σ`, and
+        // between the two chars `ß` folds to
+        let a = "xxMaßeΣ";
+        let b = "XXMASSEσ";
+        for i in 1..=b.len() {
+            for j in 1..=a.len() {
+                assert!(
+                    streams_eq_ci(chunked(a, j), chunked(b, i)).unwrap(),
+                    "chunks of {} and {}",
+                    j,
+                    i
+                );
+            }
+        }
+        assert!(!streams_eq_ci(chunked("Maße", 1), chunked("MASS", 1)).unwrap());
+        assert!(!streams_eq_ci(chunked("Maß", 1), chunked("MASSE", 2)).unwrap());
+    }
+
+    #[test]
+    fn test_streams_eq_ci_invalid_utf8() {
+        let err = streams_eq_ci(&b"ab\xFF"[..], &b"AB\xFF"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        // truncated in the middle of a char
+        let err = streams_eq_ci(&b"a\xC3"[..], &b"a\xC3"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let empty = String::new();
+        assert!(streams_eq_ci(empty.as_bytes(), &b""[..]).unwrap());
+    }
+}
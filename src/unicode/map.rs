@@ -1,6 +1,9 @@
 // Generated by scripts/mapgen.py
 // 2024-10-18
 
+// Ranges are matched without range patterns to stay compatible with old rustcs.
+#![allow(clippy::manual_range_contains, clippy::redundant_pattern)]
+
 use super::fold::Fold;
 
 use core::char;
@@ -1987,8 +1990,7 @@ fn lookup_consistency() {
             let reference: Vec<char> = lookup_naive(c).collect();
             let actual: Vec<char> = lookup(c).collect();
             if actual != reference {
-                assert!(
-                    false,
+                panic!(
                     "case-folding {:?} (#0x{:04x}) failed: Expected {:?}, got {:?}",
                     c, c_index, reference, actual
                 );
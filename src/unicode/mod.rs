@@ -2,7 +2,10 @@ use alloc::string::String;
 use core::cmp::Ordering;
 use core::hash::{Hash, Hasher};
 
-use self::map::lookup;
+use super::hash::fold_hash;
+
+pub use self::fold::Fold;
+pub use self::map::lookup;
 mod map;
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -60,47 +63,7 @@ impl<T: AsRef<str>> Ord for Unicode<T> {
 impl<S: AsRef<str>> Hash for Unicode<S> {
     #[inline]
     fn hash<H: Hasher>(&self, hasher: &mut H) {
-        let mut buf = [0; 4];
-        for c in self.0.as_ref().chars().flat_map(|c| lookup(c)) {
-            let len = char_to_utf8(c, &mut buf);
-            // we can't use `write(buf)` because the ASCII variant uses
-            // `write_u8`. The docs for Hash say that's technically different.
-            // ¯\_(ツ)_/¯
-            for &b in &buf[..len] {
-                hasher.write_u8(b);
-            }
-        }
-        // prefix-freedom
-        hasher.write_u8(0xFF);
-    }
-}
-
-#[inline]
-fn char_to_utf8(c: char, dst: &mut [u8; 4]) -> usize {
-    const TAG_CONT: u8 = 0b1000_0000;
-    const TAG_TWO_B: u8 = 0b1100_0000;
-    const TAG_THREE_B: u8 = 0b1110_0000;
-    const TAG_FOUR_B: u8 = 0b1111_0000;
-
-    let code = c as u32;
-    if code <= 0x7F {
-        dst[0] = code as u8;
-        1
-    } else if code <= 0x7FF {
-        dst[0] = (code >> 6 & 0x1F) as u8 | TAG_TWO_B;
-        dst[1] = (code & 0x3F) as u8 | TAG_CONT;
-        2
-    } else if code <= 0xFFFF {
-        dst[0] = (code >> 12 & 0x0F) as u8 | TAG_THREE_B;
-        dst[1] = (code >> 6 & 0x3F) as u8 | TAG_CONT;
-        dst[2] = (code & 0x3F) as u8 | TAG_CONT;
-        3
-    } else {
-        dst[0] = (code >> 18 & 0x07) as u8 | TAG_FOUR_B;
-        dst[1] = (code >> 12 & 0x3F) as u8 | TAG_CONT;
-        dst[2] = (code >> 6 & 0x3F) as u8 | TAG_CONT;
-        dst[3] = (code & 0x3F) as u8 | TAG_CONT;
-        4
+        fold_hash(self.0.as_ref(), hasher)
     }
 }
 
@@ -130,8 +93,8 @@ mod fold {
                     Some(one)
                 }
                 Fold::Three(one, two, three) => {
-                    *self = Fold::Two(one, two);
-                    Some(three)
+                    *self = Fold::Two(two, three);
+                    Some(one)
                 }
             }
         }
@@ -198,6 +161,8 @@ mod tests {
     #[test]
     fn test_to_folded_case() {
         assert_eq!(Unicode("Maße").to_folded_case(), "masse");
+        // three-char folds keep their order
+        assert_eq!(Unicode("\u{390}").to_folded_case(), "\u{3b9}\u{308}\u{301}");
     }
 
     #[cfg(feature = "nightly")]
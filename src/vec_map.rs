@@ -0,0 +1,159 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::mem;
+
+use super::UniCase;
+
+/// A small map with case-insensitive string keys, backed by a `Vec`.
+///
+/// Lookups scan the entries in order, comparing with the same folding as
+/// `UniCase::new`, so nothing is hashed or allocated. For a handful of keys,
+/// such as the parameters of an HTTP header, this is faster and smaller
+/// than a `HashMap`. It gets slow as the map grows, since every lookup is
+/// O(n).
+///
+/// Entries are kept in insertion order. Inserting a key that is already
+/// present, in any casing, replaces the value but keeps the original key
+/// and its position.
+///
+/// # Example
+///
+/// ```rust
+/// use unicase::CaseInsensitiveVecMap;
+///
+/// let mut params = CaseInsensitiveVecMap::new();
+/// params.insert("Charset", "utf-8");
+/// params.insert("boundary", "xyz");
+///
+/// assert_eq!(params.get("CHARSET"), Some(&"utf-8"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct CaseInsensitiveVecMap<V> {
+    entries: Vec<(UniCase<String>, V)>,
+}
+
+impl<V> CaseInsensitiveVecMap<V> {
+    /// Creates an empty map.
+    #[inline]
+    pub fn new() -> CaseInsensitiveVecMap<V> {
+        CaseInsensitiveVecMap {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Inserts a value, returning the old one if the key was already
+    /// present in any casing.
+    ///
+    /// An existing key keeps its casing and position; only its value is
+    /// replaced.
+    pub fn insert(&mut self, key: impl Into<String>, value: V) -> Option<V> {
+        let key = key.into();
+        match self.position(&key) {
+            Some(i) => Some(mem::replace(&mut self.entries[i].1, value)),
+            None => {
+                self.entries.push((UniCase::new(key), value));
+                None
+            }
+        }
+    }
+
+    /// Returns the value for a key, ignoring case.
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.position(key).map(|i| &self.entries[i].1)
+    }
+
+    /// Returns a mutable reference to the value for a key, ignoring case.
+    #[inline]
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+        match self.position(key) {
+            Some(i) => Some(&mut self.entries[i].1),
+            None => None,
+        }
+    }
+
+    /// Removes a key, ignoring case, and returns its value.
+    ///
+    /// The remaining entries keep their order.
+    pub fn remove(&mut self, key: &str) -> Option<V> {
+        self.position(key).map(|i| self.entries.remove(i).1)
+    }
+
+    /// Returns the number of entries.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the map has no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over the entries in insertion order, with keys as they were
+    /// first inserted.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &V)> {
+        self.entries.iter().map(|(k, v)| (k.as_ref(), v))
+    }
+
+    fn position(&self, key: &str) -> Option<usize> {
+        let key = UniCase::new(key);
+        self.entries.iter().position(|(k, _)| *k == key)
+    }
+}
+
+impl<V> Default for CaseInsensitiveVecMap<V> {
+    #[inline]
+    fn default() -> CaseInsensitiveVecMap<V> {
+        CaseInsensitiveVecMap::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CaseInsensitiveVecMap;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_lookup() {
+        let mut map = CaseInsensitiveVecMap::new();
+        assert!(map.is_empty());
+        map.insert("Content-Type", 1);
+        map.insert("Maße", 2);
+
+        assert_eq!(map.get("content-type"), Some(&1));
+        assert_eq!(map.get("CONTENT-TYPE"), Some(&1));
+        assert_eq!(map.get("MASSE"), Some(&2));
+        assert_eq!(map.get("Content-Length"), None);
+
+        *map.get_mut("masse").unwrap() += 10;
+        assert_eq!(map.get("maße"), Some(&12));
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn test_insert_overwrites_any_casing() {
+        let mut map = CaseInsensitiveVecMap::new();
+        assert_eq!(map.insert("Charset", "utf-8"), None);
+        assert_eq!(map.insert("CHARSET", "latin1"), Some("utf-8"));
+        assert_eq!(map.len(), 1);
+        // the first casing is kept
+        assert_eq!(map.iter().collect::<Vec<_>>(), [("Charset", &"latin1")]);
+    }
+
+    #[test]
+    fn test_insertion_order() {
+        let mut map = CaseInsensitiveVecMap::new();
+        for (i, &k) in ["c", "A", "b", "D"].iter().enumerate() {
+            map.insert(k, i);
+        }
+        map.insert("a", 10);
+        assert_eq!(map.remove("B"), Some(2));
+        assert_eq!(map.remove("b"), None);
+
+        let keys: Vec<&str> = map.iter().map(|(k, _)| k).collect();
+        assert_eq!(keys, ["c", "A", "D"]);
+        assert_eq!(map.get("a"), Some(&10));
+    }
+}
@@ -0,0 +1,98 @@
+use core::fmt;
+
+use super::unicode::lookup;
+
+/// A `fmt::Write` adapter that case-folds everything written through it.
+///
+/// Each `char` folds on its own, so text can be pushed in pieces of any size
+/// and the output is the same as folding the whole input at once. This makes
+/// it possible to fold a large document as it is read, without buffering it.
+///
+/// Folding always uses full Unicode case folding, as
+/// [`UniCase::to_folded_case`](crate::UniCase::to_folded_case) does for
+/// non-ASCII strings.
+///
+/// # Example
+///
+/// ```rust
+/// use std::fmt::Write;
+/// use unicase::FoldWriter;
+///
+/// let mut w = FoldWriter::new(String::new());
+/// w.write_str("Ma").unwrap();
+/// w.write_str("ßE").unwrap();
+/// assert_eq!(w.into_inner(), "masse");
+/// ```
+#[derive(Debug)]
+pub struct FoldWriter<W> {
+    inner: W,
+}
+
+impl<W> FoldWriter<W> {
+    /// Creates a new `FoldWriter`, writing folded text to `inner`.
+    pub const fn new(inner: W) -> FoldWriter<W> {
+        FoldWriter { inner }
+    }
+
+    /// Returns a reference to the wrapped writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps the wrapped writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: fmt::Write> fmt::Write for FoldWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            self.write_char(c)?;
+        }
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        for folded in lookup(c) {
+            self.inner.write_char(folded)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FoldWriter;
+    use crate::UniCase;
+    use core::fmt::Write;
+    use std::string::String;
+
+    #[test]
+    fn test_fold_writer_chunks() {
+        let mut w = FoldWriter::new(String::new());
+        w.write_str("Ma").unwrap();
+        w.write_str("ße").unwrap();
+        assert_eq!(w.get_ref(), "masse");
+
+        w.write_char('Σ').unwrap();
+        let n = 42;
+        write!(w, " FooBar{}!", n).unwrap();
+        assert_eq!(w.into_inner(), "masseσ foobar42!");
+    }
+
+    #[test]
+    fn test_fold_writer_matches_to_folded_case() {
+        let s = "Ünïcödé FÖÖBÄR Straße ΣΊΣΥΦΟΣ";
+        let mut w = FoldWriter::new(String::new());
+        for c in s.chars() {
+            w.write_char(c).unwrap();
+        }
+        assert_eq!(w.into_inner(), UniCase::new(s).to_folded_case());
+    }
+}
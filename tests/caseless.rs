@@ -0,0 +1,184 @@
+//! Differential tests of case folding against the `caseless` crate.
+//!
+//! `caseless` implements the matching algorithms from the Unicode standard.
+//! "Default caseless matching" compares `fold(x)`, which is exactly what
+//! `UniCase` does, so the two must agree on every input. "Canonical caseless
+//! matching" compares `NFD(fold(NFD(x)))`, and `UniCase` never normalizes,
+//! so they are only expected to agree when neither normalization step
+//! changes anything: inputs that are already in NFD, and whose folded forms
+//! are still in NFD.
+//!
+//! Known divergence classes from canonical matching, all caused by the
+//! missing normalization:
+//!
+//! - Precomposed versus decomposed text: `"é"` and `"e\u{301}"` match for
+//!   canonical matching, but not for `UniCase`.
+//! - Characters with singleton decompositions, such as the Kelvin sign
+//!   U+212A, which NFD turns into a plain `K` before folding. These still
+//!   agree in practice, because the fold table maps them to the same thing.
+//! - Combining marks that end up out of canonical order, such as an iota
+//!   subscript U+0345 followed by an accent with a lower combining class.
+//!
+//! A disagreement on an in-scope input points at a bug in the fold table or
+//! in how `Fold` yields its chars.
+
+use caseless::Caseless;
+use unicode_normalization::{is_nfd, UnicodeNormalization};
+
+use unicase::UniCase;
+
+/// Ranges with lots of cased letters, plus combining marks to mix in.
+const RANGES: &[(u32, u32)] = &[
+    (0x0020, 0x007F),
+    (0x00A0, 0x0250),
+    (0x0300, 0x0370),
+    (0x0370, 0x0530),
+    (0x10A0, 0x1100),
+    (0x1E00, 0x2000),
+    (0x2100, 0x2190),
+    (0x2C00, 0x2D30),
+    (0xA640, 0xA800),
+    (0xFB00, 0xFB07),
+    (0xFF21, 0xFF5B),
+    (0x10400, 0x10450),
+    (0x1E900, 0x1E944),
+];
+
+/// xorshift64, so failures are reproducible without a `rand` dependency.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn char(&mut self) -> char {
+        loop {
+            let (lo, hi) = RANGES[self.below(RANGES.len())];
+            let c = lo + self.below((hi - lo) as usize) as u32;
+            if let Some(c) = std::char::from_u32(c) {
+                return c;
+            }
+        }
+    }
+
+    fn string(&mut self) -> String {
+        let len = 1 + self.below(6);
+        (0..len).map(|_| self.char()).collect()
+    }
+
+    /// Randomly re-cases each char of `s`.
+    fn recase(&mut self, s: &str) -> String {
+        let mut out = String::new();
+        for c in s.chars() {
+            match self.below(3) {
+                0 => out.extend(c.to_uppercase()),
+                1 => out.extend(c.to_lowercase()),
+                _ => out.push(c),
+            }
+        }
+        out
+    }
+}
+
+fn fold(s: &str) -> String {
+    UniCase::unicode(s).to_folded_case()
+}
+
+fn in_scope(s: &str) -> bool {
+    is_nfd(s) && is_nfd(&fold(s))
+}
+
+#[test]
+fn every_char_folds_like_caseless() {
+    for c in (0..=0x10FFFF).filter_map(std::char::from_u32) {
+        let s = c.to_string();
+        let theirs: String = s.chars().default_case_fold().collect();
+        assert_eq!(fold(&s), theirs, "fold of {:?} (U+{:04X})", c, c as u32);
+    }
+}
+
+#[test]
+fn random_strings_match_like_default_caseless() {
+    let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+    let mut matched = 0;
+
+    for _ in 0..20_000 {
+        let a = rng.string();
+        let b = if rng.below(4) == 0 {
+            rng.string()
+        } else {
+            rng.recase(&a)
+        };
+
+        let ours = UniCase::new(&*a) == UniCase::new(&*b);
+        let theirs = caseless::default_caseless_match_str(&a, &b);
+        assert_eq!(ours, theirs, "comparing {:?} and {:?}", a, b);
+        if ours {
+            matched += 1;
+        }
+    }
+
+    // make sure both outcomes were actually exercised
+    assert!(matched > 1_000 && matched < 19_000, "{} matched", matched);
+}
+
+#[test]
+fn normalized_strings_match_like_canonical_caseless() {
+    let mut rng = Rng(0x2545_F491_4F6C_DD1D);
+    let mut checked = 0;
+    let mut matched = 0;
+
+    while checked < 20_000 {
+        let a: String = rng.string().nfd().collect();
+        let b: String = if rng.below(4) == 0 {
+            rng.string()
+        } else {
+            rng.recase(&a)
+        };
+        let b: String = b.nfd().collect();
+        if !in_scope(&a) || !in_scope(&b) {
+            continue;
+        }
+
+        let ours = UniCase::new(&*a) == UniCase::new(&*b);
+        let theirs = caseless::canonical_caseless_match_str(&a, &b);
+        assert_eq!(ours, theirs, "comparing {:?} and {:?}", a, b);
+
+        checked += 1;
+        if ours {
+            matched += 1;
+        }
+    }
+
+    assert!(matched > 1_000 && matched < 19_000, "{} matched", matched);
+}
+
+#[test]
+fn known_canonical_divergences() {
+    let cases = [
+        // precomposed versus decomposed
+        ("\u{e9}", "e\u{301}"),
+        ("\u{c5}", "A\u{30a}"),
+        // NFD moves the iota subscript behind the acute accent
+        ("\u{1fb3}\u{301}", "\u{1fb4}"),
+    ];
+    for &(a, b) in &cases {
+        assert!(!in_scope(a) || !in_scope(b), "{:?} {:?}", a, b);
+        assert!(
+            caseless::canonical_caseless_match_str(a, b),
+            "{:?} {:?}",
+            a,
+            b
+        );
+        assert!(!caseless::default_caseless_match_str(a, b));
+        assert!(UniCase::new(a) != UniCase::new(b), "{:?} {:?}", a, b);
+    }
+}
@@ -0,0 +1,65 @@
+//! Checks that ordering and comparisons work from a `no_std` crate.
+//!
+//! The test harness itself needs `std`, but nothing in this file may use it:
+//! no `HashMap`, no `DefaultHasher`, no `String`. Only `core` is in scope,
+//! so anything that compiles here is usable on `no_std` targets.
+#![no_std]
+
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+
+use unicase::{Ascii, UniCase};
+
+/// A tiny FNV-1a hasher, so hashing can be checked without `std`.
+struct Fnv(u64);
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= u64::from(b);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+fn hash<T: Hash>(t: &T) -> u64 {
+    let mut h = Fnv(0xcbf2_9ce4_8422_2325);
+    t.hash(&mut h);
+    h.finish()
+}
+
+#[test]
+fn ascii_ordering() {
+    assert_eq!(Ascii::new("a").cmp(&Ascii::new("B")), Ordering::Less);
+    assert_eq!(Ascii::new("FOO").cmp(&Ascii::new("foo")), Ordering::Equal);
+    assert!(Ascii::new("aa") > Ascii::new("A"));
+
+    let mut words = [Ascii::new("b"), Ascii::new("C"), Ascii::new("A")];
+    words.sort_unstable();
+    assert_eq!(words, [Ascii::new("a"), Ascii::new("b"), Ascii::new("c")]);
+}
+
+#[test]
+fn unicase_ordering() {
+    assert!(UniCase::new("a") < UniCase::new("B"));
+    assert!(UniCase::new("Maße") > UniCase::new("MASS"));
+    assert_eq!(
+        UniCase::new("Maße").cmp(&UniCase::new("MASSE")),
+        Ordering::Equal
+    );
+}
+
+#[test]
+fn eq_and_hash() {
+    assert_eq!(Ascii::new("FooBar"), Ascii::new("fOObAR"));
+    assert_eq!(hash(&Ascii::new("FooBar")), hash(&Ascii::new("fOObAR")));
+
+    assert_eq!(UniCase::new("Maße"), UniCase::new("MASSE"));
+    assert_eq!(hash(&UniCase::new("Maße")), hash(&UniCase::new("MASSE")));
+    assert!(unicase::eq("Σ", "σ"));
+    assert!(unicase::eq_ascii("ABC", "abc"));
+}
@@ -0,0 +1,89 @@
+//! Checks folding and hashing against checked-in snapshots.
+//!
+//! Downstream users precompute hashes of `UniCase` keys (for instance in
+//! `phf` maps built at compile time), so any change to the fold table or to
+//! the bytes fed into the hasher is a breaking change. The fixture in
+//! `tests/snapshots/fold_hash.txt` pins both.
+//!
+//! Each non-comment line holds three tab-separated fields: the input, its
+//! folded form, and the hash of `UniCase::new(input)` under 64-bit FNV-1a,
+//! in hex. FNV-1a is used because, unlike `DefaultHasher`, its output is
+//! fixed and it is sensitive to every byte written.
+//!
+//! If a change is intentional, run the tests with `UNICASE_BLESS=1` to
+//! rewrite the fixture, and review the diff.
+
+use std::env;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+use unicase::UniCase;
+
+const FIXTURE: &str = "tests/snapshots/fold_hash.txt";
+
+struct Fnv1a(u64);
+
+impl Hasher for Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= u64::from(b);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+fn fnv_hash<T: Hash>(t: &T) -> u64 {
+    let mut hasher = Fnv1a(0xcbf2_9ce4_8422_2325);
+    t.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn snapshot_line(input: &str) -> String {
+    let key = UniCase::new(input);
+    format!(
+        "{}\t{}\t{:016x}",
+        input,
+        key.to_folded_case(),
+        fnv_hash(&key)
+    )
+}
+
+#[test]
+fn test_fold_hash_snapshots() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(FIXTURE);
+    let fixture = fs::read_to_string(&path).expect("read fixture");
+
+    let mut expected = String::new();
+    let mut mismatches = Vec::new();
+    for line in fixture.lines() {
+        if line.starts_with('#') || line.is_empty() {
+            expected.push_str(line);
+        } else {
+            let input = line.split('\t').next().unwrap();
+            let actual = snapshot_line(input);
+            if actual != line {
+                mismatches.push(format!("  expected: {:?}\n    actual: {:?}", line, actual));
+            }
+            expected.push_str(&actual);
+        }
+        expected.push('\n');
+    }
+
+    if env::var_os("UNICASE_BLESS").is_some() {
+        fs::write(&path, expected).expect("write fixture");
+        return;
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "folding or hashing changed for {} snapshot(s); \
+         rerun with UNICASE_BLESS=1 if this is intended:\n{}",
+        mismatches.len(),
+        mismatches.join("\n")
+    );
+}